@@ -0,0 +1,126 @@
+//! Times day 7 on generated terminal sessions of growing size: deep random trees, a single
+//! directory listing all the files, and a single directory listing all the subdirectories.
+//! In every shape the time per entry should stay within a small factor from a thousand to
+//! half a million entries (large maps fit worse in the caches) instead of growing with the
+//! size, showing that building the tree and solving both parts are linear.
+//!
+//! Run with `cargo bench --bench day7`.
+
+use aoc22::day7::{Day7, FileSystem};
+use aoc22::Solution;
+use std::time::{Duration, Instant};
+
+const SIZES: [usize; 4] = [1_000, 10_000, 100_000, 500_000];
+const RUNS: usize = 5;
+
+/// Small linear congruential generator, enough to vary the shape of the tree
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self, bound: usize) -> usize {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((self.0 >> 33) % bound as u64) as usize
+    }
+}
+
+/// Session walking a random tree of about `entries` files and directories depth first, listing
+/// every directory once on the way down
+fn session(entries: usize) -> String {
+    let mut rng = Lcg(entries as u64);
+    let mut lines = Vec::new();
+    // subdirectories left to visit at each level of the walk, `None` going back up
+    let mut stack = vec![Some("/".to_owned())];
+    let mut listed = 0;
+    let mut next_dir = 0;
+    while let Some(dir) = stack.pop() {
+        match dir {
+            Some(name) => lines.push(format!("$ cd {name}")),
+            None if stack.is_empty() => break,
+            None => {
+                lines.push("$ cd ..".to_owned());
+                continue;
+            }
+        }
+        lines.push("$ ls".to_owned());
+        for file in 0..rng.next(8) {
+            lines.push(format!("{} f{file}.txt", 1 + rng.next(300_000)));
+            listed += 1;
+        }
+        // stop adding directories once there are enough entries, the walk then winds down
+        let dirs = if listed < entries && stack.len() < 40 { rng.next(4) + 1 } else { 0 };
+        stack.push(None);
+        for _ in 0..dirs {
+            lines.push(format!("dir d{next_dir}"));
+            stack.push(Some(format!("d{next_dir}")));
+            next_dir += 1;
+            listed += 1;
+        }
+    }
+    lines.join("\n")
+}
+
+/// Session listing `entries` files in the root
+fn wide_files(entries: usize) -> String {
+    let files: Vec<String> = (0..entries)
+        .map(|file| format!("{} f{file}.txt", 1 + file % 300_000))
+        .collect();
+    format!("$ cd /\n$ ls\n{}", files.join("\n"))
+}
+
+/// Session listing `entries / 2` subdirectories in the root, each holding one file
+fn wide_dirs(entries: usize) -> String {
+    let mut lines = vec!["$ cd /".to_owned(), "$ ls".to_owned()];
+    lines.extend((0..entries / 2).map(|dir| format!("dir d{dir}")));
+    for dir in 0..entries / 2 {
+        lines.push(format!("$ cd d{dir}"));
+        lines.push("$ ls".to_owned());
+        lines.push(format!("{} f", 1 + dir % 300_000));
+        lines.push("$ cd ..".to_owned());
+    }
+    lines.join("\n")
+}
+
+/// Session of about the given number of entries
+type Generator = fn(usize) -> String;
+
+fn main() {
+    println!(
+        "{:>8} {:>10} {:>12} {:>12} {:>14}",
+        "shape", "entries", "build", "parts", "ns per entry"
+    );
+    let shapes: [(&str, Generator); 3] = [
+        ("deep", session),
+        ("files", wide_files),
+        ("dirs", wide_dirs),
+    ];
+    for (shape, generate) in shapes {
+        for entries in SIZES {
+            let input = generate(entries);
+            let mut build = Duration::MAX;
+            let mut parts = Duration::MAX;
+            for _ in 0..RUNS {
+                let start = Instant::now();
+                let fs = FileSystem::new()
+                    .build(&input)
+                    .expect("generated session is valid");
+                build = build.min(start.elapsed());
+
+                let start = Instant::now();
+                std::hint::black_box((Day7::part1(&fs), Day7::part2(&fs)));
+                parts = parts.min(start.elapsed());
+            }
+            let lines = input.lines().filter(|line| !line.starts_with('$')).count();
+            println!(
+                "{:>8} {:>10} {:>12.3?} {:>12.3?} {:>14.1}",
+                shape,
+                lines,
+                build,
+                parts,
+                (build + parts).as_nanos() as f64 / lines as f64
+            );
+        }
+    }
+}
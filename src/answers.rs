@@ -0,0 +1,230 @@
+use crate::runner::DayReport;
+use std::collections::HashMap;
+
+/// Expected answers keyed by (day, part)
+pub type Answers = HashMap<(u8, u8), String>;
+
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Missing,
+}
+
+fn unescape(s: &str) -> Result<String, String> {
+    let mut result = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('"') => result.push('"'),
+            Some('\\') => result.push('\\'),
+            other => return Err(format!("unsupported escape `\\{}`", other.unwrap_or(' '))),
+        }
+    }
+    Ok(result)
+}
+
+/// Parses the subset of TOML used by `inputs/answers.toml`:
+/// `[dayN]` tables with `partN = ...` keys holding integers, "strings" or """multiline strings""".
+pub fn parse(contents: &str) -> Result<Answers, String> {
+    let mut answers = Answers::new();
+    let mut day: Option<u8> = None;
+    let mut lines = contents.lines().enumerate();
+
+    while let Some((idx, line)) = lines.next() {
+        let line_number = idx + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(table) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            day = match table.trim().strip_prefix("day").map(|d| d.parse::<u8>()) {
+                Some(Ok(d)) => Some(d),
+                _ => return Err(format!("line {line_number}: expected `[dayN]`, found `{line}`")),
+            };
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or(format!("line {line_number}: expected `partN = answer`, found `{line}`"))?;
+        let part = match key.trim() {
+            "part1" => 1,
+            "part2" => 2,
+            other => return Err(format!("line {line_number}: unknown key `{other}`")),
+        };
+        let day = day.ok_or(format!("line {line_number}: `{}` outside of a `[dayN]` table", key.trim()))?;
+        let value = value.trim();
+
+        let answer = if let Some(rest) = value.strip_prefix("\"\"\"") {
+            // a newline right after the opening quotes is not part of the string
+            let mut raw = rest.to_owned();
+            while !raw.ends_with("\"\"\"") {
+                let (_, next) = lines
+                    .next()
+                    .ok_or(format!("line {line_number}: unterminated multiline string"))?;
+                if !raw.is_empty() {
+                    raw.push('\n');
+                }
+                raw.push_str(next);
+            }
+            unescape(&raw[..raw.len() - 3]).map_err(|e| format!("line {line_number}: {e}"))?
+        } else if let Some(s) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+            unescape(s).map_err(|e| format!("line {line_number}: {e}"))?
+        } else if value.parse::<i64>().is_ok() {
+            value.to_owned()
+        } else {
+            return Err(format!("line {line_number}: invalid value `{value}`"));
+        };
+        answers.insert((day, part), answer);
+    }
+    Ok(answers)
+}
+
+pub fn verify(answers: &Answers, day: u8, part: u8, answer: &str) -> Verdict {
+    match answers.get(&(day, part)) {
+        Some(expected) if expected == answer => Verdict::Pass,
+        Some(expected) => Verdict::Fail {
+            expected: expected.to_owned(),
+        },
+        None => Verdict::Missing,
+    }
+}
+
+/// Pass/fail line for every computed part, followed by the totals.
+/// Returns the report and the number of days with a wrong answer or an error.
+pub fn report(answers: &Answers, reports: &[DayReport]) -> (String, usize) {
+    let mut lines: Vec<String> = Vec::new();
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    let mut failed_days = 0;
+    for report in reports {
+        let run = match &report.outcome {
+            Ok(day_runs) => &day_runs[0],
+            Err(_) => {
+                failed += 1;
+                failed_days += 1;
+                let error = report.error_summary().unwrap_or_default();
+                lines.push(format!("Day {}: FAILED ({})", report.day, error));
+                continue;
+            }
+        };
+        let failed_before = failed;
+        for part in &run.parts {
+            let verdict = match verify(answers, run.day, part.part, &part.answer) {
+                Verdict::Pass => {
+                    passed += 1;
+                    "PASS".to_owned()
+                }
+                Verdict::Fail { expected } => {
+                    failed += 1;
+                    format!("FAIL (expected {:?}, got {:?})", expected, part.answer)
+                }
+                Verdict::Missing => {
+                    missing += 1;
+                    "MISSING (no stored answer)".to_owned()
+                }
+            };
+            lines.push(format!("Day {}, Part {}: {}", run.day, part.part, verdict));
+        }
+        if failed > failed_before {
+            failed_days += 1;
+        }
+    }
+    lines.push("---".to_owned());
+    lines.push(format!("{passed} passed, {failed} failed, {missing} missing"));
+    (lines.join("\n"), failed_days)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answers::{self, Verdict};
+    use crate::runner::{DayReport, DayRun, PartRun};
+    use std::time::Duration;
+
+    const TEST_INPUT: &str = r##"
+# expected answers
+[day1]
+part1 = 68923
+part2 = "200044"
+
+[day10]
+part1 = "14340"
+part2 = """
+##..
+.\"#."""
+"##;
+
+    #[test]
+    fn parse() {
+        let answers = answers::parse(TEST_INPUT).unwrap();
+        assert_eq!(answers.len(), 4);
+        assert_eq!(answers[&(1, 1)], "68923");
+        assert_eq!(answers[&(1, 2)], "200044");
+        assert_eq!(answers[&(10, 1)], "14340");
+        assert_eq!(answers[&(10, 2)], "##..\n.\"#.");
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(answers::parse("part1 = 1").is_err());
+        assert!(answers::parse("[day1]\npart3 = 1").is_err());
+        assert!(answers::parse("[day1]\npart1 = abc").is_err());
+        assert!(answers::parse("[dayx]").is_err());
+        assert!(answers::parse("[day1]\npart2 = \"\"\"\n##").is_err());
+    }
+
+    #[test]
+    fn verify() {
+        let answers = answers::parse(TEST_INPUT).unwrap();
+        assert_eq!(answers::verify(&answers, 1, 1, "68923"), Verdict::Pass);
+        assert_eq!(
+            answers::verify(&answers, 1, 2, "1"),
+            Verdict::Fail { expected: "200044".to_owned() }
+        );
+        assert_eq!(answers::verify(&answers, 13, 1, "0"), Verdict::Missing);
+    }
+
+    #[test]
+    fn report() {
+        let answers = answers::parse(TEST_INPUT).unwrap();
+        let run = |day: u8, answers: [&str; 2]| DayRun {
+            day,
+            read: Duration::ZERO,
+            parse: Duration::ZERO,
+            parts: (1..=2)
+                .map(|part| PartRun {
+                    part,
+                    answer: answers[part as usize - 1].to_owned(),
+                    duration: Duration::ZERO,
+                })
+                .collect(),
+            warnings: Vec::new(),
+        };
+        let reports = vec![
+            DayReport { day: 1, outcome: Ok(vec![run(1, ["68923", "1"])]) },
+            DayReport { day: 2, outcome: Ok(vec![run(2, ["10404", "10334"])]) },
+            DayReport { day: 10, outcome: Err("error: boom\n --> here".to_owned()) },
+        ];
+        let (report, failed_days) = answers::report(&answers, &reports);
+        println!("{}", report);
+        assert_eq!(failed_days, 2);
+        assert_eq!(
+            report.lines().collect::<Vec<&str>>(),
+            vec![
+                "Day 1, Part 1: PASS",
+                "Day 1, Part 2: FAIL (expected \"200044\", got \"1\")",
+                "Day 2, Part 1: MISSING (no stored answer)",
+                "Day 2, Part 2: MISSING (no stored answer)",
+                "Day 10: FAILED (error: boom)",
+                "---",
+                "1 passed, 2 failed, 2 missing",
+            ]
+        );
+    }
+}
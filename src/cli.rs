@@ -0,0 +1,236 @@
+use crate::output::Format;
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+Usage: aoc22 [DAYS...] [--part <1|2>] [--repeat <N>] [--format <json|csv|text>] [--verify]
+             [--input-dir <DIR>] [--input [DAY=]<PATH>]
+
+DAYS is a day number (`11`) or an inclusive range (`3..8`, `3..=8`).
+Without DAYS every solved day runs.
+
+Inputs are read from DIR/dayN.txt, where DIR is `--input-dir`, then $AOC22_INPUT_DIR,
+then ./inputs. `--input` overrides the file of a single day, `-` reads it from stdin.
+
+Options:
+  -p, --part <1|2>          only compute the given part
+  -r, --repeat <N>          run every day N times and report min, median and max timings
+  -f, --format <F>          print answers and timings as json, csv or text (default)
+      --verify              compare the answers with answers.toml in the input folder,
+                            exit nonzero on a mismatch, only with the text format
+  -i, --input-dir <DIR>     folder with the dayN.txt inputs and answers.toml
+      --input [DAY=]<PATH>  input file for DAY, DAY can be left out when a single day runs
+  -h, --help                print this message";
+
+/// Exit code of a command line error, above any number of failed days
+pub const EXIT_USAGE: i32 = 64;
+
+/// Exit code when `--verify` can't read the answers
+pub const EXIT_NO_ANSWERS: i32 = 66;
+
+/// Exit code for `failed` days, kept below the other exit codes
+pub fn exit_code(failed: usize) -> i32 {
+    failed.min(EXIT_USAGE as usize - 1) as i32
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Args {
+    pub days: Vec<u8>,
+    pub part: Option<u8>,
+    pub repeat: usize,
+    pub format: Format,
+    pub verify: bool,
+    pub input_dir: Option<PathBuf>,
+    pub inputs: HashMap<u8, PathBuf>,
+    pub help: bool,
+}
+
+impl Args {
+    /// Parts selected with `--part`, both by default
+    pub fn parts(&self) -> Vec<u8> {
+        match self.part {
+            Some(part) => vec![part],
+            None => vec![1, 2],
+        }
+    }
+}
+
+fn parse_day(s: &str) -> Result<u8, String> {
+    s.parse::<u8>().map_err(|_| format!("invalid day `{s}`"))
+}
+
+fn parse_days(s: &str) -> Result<RangeInclusive<u8>, String> {
+    let range = match s.split_once("..") {
+        Some((from, to)) => parse_day(from)?..=parse_day(to.trim_start_matches('='))?,
+        None => parse_day(s)?..=parse_day(s)?,
+    };
+    if range.is_empty() {
+        return Err(format!("empty day range `{s}`"));
+    }
+    Ok(range)
+}
+
+/// Parses the command line arguments (without the program name).
+/// `all_days` is used when no day is selected.
+pub fn parse_args<I: Iterator<Item = String>>(mut args: I, all_days: &[u8]) -> Result<Args, String> {
+    let mut days: Vec<u8> = Vec::new();
+    let mut part: Option<u8> = None;
+    let mut repeat: usize = 1;
+    let mut format = Format::Text;
+    let mut verify = false;
+    let mut input_dir: Option<PathBuf> = None;
+    let mut day_inputs: Vec<(Option<u8>, PathBuf)> = Vec::new();
+    let mut help = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => help = true,
+            "--verify" => verify = true,
+            "-p" | "--part" => {
+                let value = args.next().ok_or("missing value for `--part`")?;
+                part = match value.as_str() {
+                    "1" => Some(1),
+                    "2" => Some(2),
+                    _ => return Err(format!("invalid part `{value}`, expected 1 or 2")),
+                };
+            }
+            "-r" | "--repeat" => {
+                let value = args.next().ok_or("missing value for `--repeat`")?;
+                repeat = match value.parse::<usize>() {
+                    Ok(n) if n > 0 => n,
+                    _ => return Err(format!("invalid repeat count `{value}`")),
+                };
+            }
+            "-f" | "--format" => {
+                format = args.next().ok_or("missing value for `--format`")?.parse()?;
+            }
+            "-i" | "--input-dir" => {
+                input_dir = Some(args.next().ok_or("missing value for `--input-dir`")?.into());
+            }
+            "--input" => {
+                let value = args.next().ok_or("missing value for `--input`")?;
+                day_inputs.push(match value.split_once('=') {
+                    Some((day, path)) => (Some(parse_day(day)?), path.into()),
+                    None => (None, value.into()),
+                });
+            }
+            flag if flag.starts_with('-') => return Err(format!("unknown option `{flag}`")),
+            spec => days.extend(parse_days(spec)?),
+        }
+    }
+
+    if days.is_empty() {
+        days = all_days.to_vec();
+    }
+    days.sort();
+    days.dedup();
+    if let Some(day) = days.iter().find(|d| !all_days.contains(d)) {
+        return Err(format!("day {day} is not solved yet"));
+    }
+    if verify && format != Format::Text {
+        return Err("`--verify` prints text, it can't be combined with `--format json` or `--format csv`".to_owned());
+    }
+
+    let mut inputs: HashMap<u8, PathBuf> = HashMap::new();
+    for (day, path) in day_inputs {
+        let day = match (day, days.as_slice()) {
+            (Some(day), _) if days.contains(&day) => day,
+            (Some(day), _) => return Err(format!("`--input` for day {day}, which is not selected")),
+            (None, [day]) => *day,
+            (None, _) => return Err("`--input` without a day needs exactly one selected day".to_owned()),
+        };
+        inputs.insert(day, path);
+    }
+    let stdin_days = inputs.values().filter(|path| path.as_os_str() == "-").count();
+    if stdin_days > 1 {
+        return Err("only one day can read its input from stdin".to_owned());
+    }
+
+    Ok(Args {
+        days,
+        part,
+        repeat,
+        format,
+        verify,
+        input_dir,
+        inputs,
+        help,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli;
+    use crate::output::Format;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    const ALL_DAYS: [u8; 13] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13];
+
+    fn parse(args: &[&str]) -> Result<cli::Args, String> {
+        cli::parse_args(args.iter().map(|a| a.to_string()), &ALL_DAYS)
+    }
+
+    #[test]
+    fn parse_args() {
+        assert_eq!(parse(&[]).unwrap().days, ALL_DAYS.to_vec());
+        assert_eq!(parse(&["11"]).unwrap().days, vec![11]);
+        assert_eq!(parse(&["3..8"]).unwrap().days, vec![3, 4, 5, 6, 7, 8]);
+        assert_eq!(parse(&["3..=5", "1"]).unwrap().days, vec![1, 3, 4, 5]);
+        assert_eq!(parse(&["11", "--part", "2"]).unwrap().part, Some(2));
+        assert_eq!(parse(&["-p", "1"]).unwrap().part, Some(1));
+        assert_eq!(parse(&["-p", "1"]).unwrap().parts(), vec![1]);
+        assert_eq!(parse(&[]).unwrap().parts(), vec![1, 2]);
+        assert_eq!(parse(&[]).unwrap().repeat, 1);
+        assert_eq!(parse(&["12", "--repeat", "10"]).unwrap().repeat, 10);
+        assert_eq!(parse(&[]).unwrap().format, Format::Text);
+        assert_eq!(parse(&["--format", "json"]).unwrap().format, Format::Json);
+        assert_eq!(parse(&["-f", "csv"]).unwrap().format, Format::Csv);
+        assert!(!parse(&[]).unwrap().verify);
+        assert!(parse(&["1..3", "--verify"]).unwrap().verify);
+        assert_eq!(parse(&["-i", "/tmp/bob"]).unwrap().input_dir, Some(PathBuf::from("/tmp/bob")));
+        assert_eq!(parse(&[]).unwrap().input_dir, None);
+    }
+
+    #[test]
+    fn parse_args_inputs() {
+        let args = parse(&["7", "--input", "-"]).unwrap();
+        assert_eq!(args.inputs, HashMap::from([(7, PathBuf::from("-"))]));
+        let args = parse(&["--input", "7=a.txt", "--input", "8=b.txt"]).unwrap();
+        assert_eq!(
+            args.inputs,
+            HashMap::from([(7, PathBuf::from("a.txt")), (8, PathBuf::from("b.txt"))])
+        );
+        assert!(parse(&["--input", "a.txt"]).is_err());
+        assert!(parse(&["--input", "7=-", "--input", "8=-"]).is_err());
+        assert_eq!(
+            parse(&["8", "--input", "7=a.txt"]),
+            Err("`--input` for day 7, which is not selected".to_owned())
+        );
+        assert!(parse(&["7", "8", "--input", "7=a.txt"]).is_ok());
+    }
+
+    #[test]
+    fn exit_code() {
+        assert_eq!(cli::exit_code(0), 0);
+        assert_eq!(cli::exit_code(2), 2);
+        assert!(cli::exit_code(1000) < cli::EXIT_USAGE);
+        assert!(cli::exit_code(1000) < cli::EXIT_NO_ANSWERS);
+    }
+
+    #[test]
+    fn parse_args_errors() {
+        assert!(parse(&["--part", "3"]).is_err());
+        assert!(parse(&["--part"]).is_err());
+        assert!(parse(&["--repeat", "0"]).is_err());
+        assert!(parse(&["--format", "yaml"]).is_err());
+        assert!(parse(&["8..3"]).is_err());
+        assert!(parse(&["25"]).is_err());
+        assert!(parse(&["x"]).is_err());
+        assert!(parse(&["--verbose"]).is_err());
+        assert!(parse(&["--verify", "--format", "json"]).is_err());
+        assert!(parse(&["--verify", "-f", "csv"]).is_err());
+        assert!(parse(&["--verify", "--format", "text"]).is_ok());
+    }
+}
@@ -1,30 +1,44 @@
-use crate::helpers::read_file;
-
-fn parser() -> Vec<Vec<u32>> {
-    let contents = read_file("/inputs/day1.txt");
+use crate::error::{lines, ParseError};
+use crate::solution::Solution;
 
+pub fn parser(contents: &str) -> Result<Vec<Vec<u32>>, ParseError> {
     let mut result: Vec<Vec<u32>> = Vec::new();
     let mut subarray = Vec::new();
-    for l in contents.lines() {
-        if l.is_empty() {
+    for l in lines(1, contents) {
+        if l.text.is_empty() {
             result.push(subarray);
             subarray = Vec::new();
         } else {
-            subarray.push(l.parse::<u32>().unwrap());
+            subarray.push(l.parse::<u32>(l.text, "a number of calories")?);
         }
     }
     result.push(subarray);
 
-    return result
+    return Ok(result)
 }
 
-pub fn solution() -> (String, String) {
-    let vector = parser();
+pub fn top_three(vector: &[Vec<u32>]) -> Vec<u32> {
     let mut map_of_sums = vector.iter().map(|x| x.iter().sum::<u32>()).collect::<Vec<u32>>();
     map_of_sums.sort();
-    let top_three = map_of_sums.into_iter().rev().take(3).collect::<Vec<u32>>();
-    let max = top_three.iter().max();
-    let sum = top_three.iter().sum::<u32>();
-    
-    return (max.unwrap().to_string(), sum.to_string())
-}
\ No newline at end of file
+    map_of_sums.into_iter().rev().take(3).collect::<Vec<u32>>()
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<Vec<u32>>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parser(input)
+    }
+
+    fn part1(input: &Self::Input) -> String {
+        let max = top_three(input).into_iter().max();
+        return max.unwrap().to_string();
+    }
+
+    fn part2(input: &Self::Input) -> String {
+        let sum = top_three(input).iter().sum::<u32>();
+        return sum.to_string();
+    }
+}
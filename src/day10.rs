@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
-use crate::helpers::read_file;
+use crate::error::{lines, ParseError};
+use crate::solution::Solution;
 
 /// (number of cycles, register value increase/decrease)
 type Operation = (usize, i32);
@@ -9,25 +10,24 @@ type Operation = (usize, i32);
 type SignalWave = Vec<(usize, i32)>;
 
 #[derive(Debug)]
-struct Register {
+pub struct Register {
     operations: Vec<Operation>,
     signal_wave: SignalWave,
 }
 
 impl Register {
-    fn parse(ops: &str) -> Register {
+    pub fn parse(ops: &str) -> Result<Register, ParseError> {
         let mut register = Register {
-            operations: ops
-                .lines()
-                .map(|op_str| match op_str {
-                    "noop" => (1, 0),
-                    _ => (2, op_str.split_once(" ").unwrap().1.parse::<i32>().unwrap()),
+            operations: lines(10, ops)
+                .map(|op| match op.text {
+                    "noop" => Ok((1, 0)),
+                    _ => Ok((2, op.parse::<i32>(op.strip_prefix("addx ")?, "a number")?)),
                 })
-                .collect(),
+                .collect::<Result<Vec<Operation>, ParseError>>()?,
             signal_wave: Vec::new(),
         };
         register.signal_wave = register.get_signal_wave();
-        register
+        Ok(register)
     }
 
     /// Return SignalWave for this register
@@ -45,7 +45,7 @@ impl Register {
         signal_wave
     }
 
-    fn get_x_at_cycles(&self, cycles: Vec<usize>) -> Vec<(usize, i32)> {
+    pub fn get_x_at_cycles(&self, cycles: Vec<usize>) -> Vec<(usize, i32)> {
         let mut sorted_cycles = cycles.clone();
         sorted_cycles.sort();
 
@@ -68,23 +68,22 @@ impl Register {
         let mut i = 1;
         for (cycle, x) in &self.signal_wave {
             while v.len() <= *cycle {
-                v.insert(i, x.clone());
+                v.insert(i, *x);
                 i += 1;
             }
         }
         v
     }
 
-
     /// each cycle draw a pixel: either "#" or "." if it's within the sprite position
     /// sprite is 3 pixels wide and positioned at current register
-    fn draw_screen(&self) -> String {
+    pub fn draw_screen(&self) -> String {
         let mut result: Vec<String> = vec![];
         let sprite_positions = self.get_x_per_cycle();
         let max_cycle = sprite_positions.keys().max().unwrap();
 
         for cycle in 1..(max_cycle - 1) {
-            let sprite_pos: usize = sprite_positions.get(&cycle).unwrap().clone() as usize;
+            let sprite_pos: usize = *sprite_positions.get(&cycle).unwrap() as usize;
             let crt_pos = cycle % 40;
             if crt_pos >= sprite_pos && crt_pos <= (sprite_pos + 2) {
                 result.push("#".to_owned());
@@ -99,42 +98,54 @@ impl Register {
     }
 }
 
-pub fn solution() -> (String, String) {
-    let contents = read_file("/inputs/day10.txt");
+pub struct Day10;
 
-    let register = Register::parse(&contents);
+impl Solution for Day10 {
+    type Input = Register;
 
-    let max_cycle = register.signal_wave.last().unwrap().0;
-    let cycles: Vec<usize> = (20..max_cycle)
-        .filter(|cycle| (cycle - 20) % 40 == 0)
-        .collect();
-    let x_at_cycles = register.get_x_at_cycles(cycles);
-    let result1: i32 = x_at_cycles
-        .iter()
-        .map(|(cycle, signal)| (*cycle as i32) * signal)
-        .sum();
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Register::parse(input)
+    }
 
-    let result2: String = vec!["\n".to_owned(), register.draw_screen()].join("");
+    fn part1(input: &Self::Input) -> String {
+        let max_cycle = input.signal_wave.last().unwrap().0;
+        let cycles: Vec<usize> = (20..max_cycle)
+            .filter(|cycle| (cycle - 20) % 40 == 0)
+            .collect();
+        let x_at_cycles = input.get_x_at_cycles(cycles);
+        let result1: i32 = x_at_cycles
+            .iter()
+            .map(|(cycle, signal)| (*cycle as i32) * signal)
+            .sum();
 
-    return (result1.to_string(), result2);
+        return result1.to_string();
+    }
+
+    fn part2(input: &Self::Input) -> String {
+        let result2: String = input.draw_screen().trim_end().to_owned();
+
+        return result2;
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::day10;
     use crate::day10_test::PARSED_OPERATIONS;
+    use crate::helpers::read_input;
+    use std::path::Path;
 
     #[test]
     fn parse() {
-        let test_input: String = day10::read_file("/inputs/day10_test.txt");
-        let register = day10::Register::parse(&test_input);
+        let test_input: String = read_input(Path::new("inputs/day10_test.txt")).unwrap();
+        let register = day10::Register::parse(&test_input).unwrap();
         assert_eq!(register.operations, PARSED_OPERATIONS)
     }
 
     #[test]
     fn get_signal_wave() {
-        let test_input: String = day10::read_file("/inputs/day10_test.txt");
-        let register = day10::Register::parse(&test_input);
+        let test_input: String = read_input(Path::new("inputs/day10_test.txt")).unwrap();
+        let register = day10::Register::parse(&test_input).unwrap();
 
         let expected_x_at_cycles: Vec<(usize, i32)> = vec![
             (20, 21),
@@ -153,11 +164,11 @@ mod tests {
 
     #[test]
     fn get_x_per_cycle() {
-        let test_input: String = day10::read_file("/inputs/day10_test.txt");
-        let register = day10::Register::parse(&test_input);
+        let test_input: String = read_input(Path::new("inputs/day10_test.txt")).unwrap();
+        let register = day10::Register::parse(&test_input).unwrap();
         let x_per_cycles = register.get_x_per_cycle();
         let mut sorted_x_per_cycles = x_per_cycles.iter().collect::<Vec<_>>();
-        sorted_x_per_cycles.sort_by(|(a, _), (b, _)| a.cmp(b));
+        sorted_x_per_cycles.sort_by_key(|(cycle, _)| **cycle);
         for (cycle, x) in sorted_x_per_cycles {
             println!("cycle: {}, x: {}", cycle, x)
         }
@@ -171,16 +182,16 @@ mod tests {
 
     #[test]
     fn draw_screen() {
-        let test_input: String = day10::read_file("/inputs/day10_test.txt");
-        let register = day10::Register::parse(&test_input);
+        let test_input: String = read_input(Path::new("inputs/day10_test.txt")).unwrap();
+        let register = day10::Register::parse(&test_input).unwrap();
         let screen = register.draw_screen();
         println!("{}", screen);
     }
 
     #[test]
     fn part_1() {
-        let test_input: String = day10::read_file("/inputs/day10_test.txt");
-        let register = day10::Register::parse(&test_input);
+        let test_input: String = read_input(Path::new("inputs/day10_test.txt")).unwrap();
+        let register = day10::Register::parse(&test_input).unwrap();
 
         let max_cycle = register.signal_wave.last().unwrap().0;
         println!("{:?}", max_cycle);
@@ -199,4 +210,15 @@ mod tests {
 
         assert_eq!(result1.iter().sum::<i32>(), 13140);
     }
+
+    #[test]
+    fn parse_errors() {
+        let error = day10::Register::parse("noop\nmulx 3").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.expected, "`addx `");
+
+        let error = day10::Register::parse("addx 3\naddx -x").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 6));
+        assert_eq!(error.found, "`-x`");
+    }
 }
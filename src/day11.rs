@@ -1,20 +1,22 @@
-use crate::helpers::read_file;
+use crate::error::{lines, Line, ParseError};
+use crate::solution::Solution;
 
 #[derive(Debug, PartialEq, Clone)]
-enum OperationType {
+pub enum OperationType {
     Multiplication,
     Addition,
 }
 
 #[derive(Debug, Default, Clone)]
-struct MonkeyTest {
+pub struct MonkeyTest {
     divisible_by: u64,
     true_monkey: usize,
     false_monkey: usize,
 }
 
+#[allow(dead_code)]
 #[derive(Debug, Default, Clone)]
-struct Monkey {
+pub struct Monkey {
     idx: usize,
     items: Vec<u64>,
     operation_type: Option<OperationType>,
@@ -23,7 +25,7 @@ struct Monkey {
     inspections: u64,
 }
 
-type Jungle = Vec<Monkey>;
+pub type Jungle = Vec<Monkey>;
 
 trait Player {
     fn inspect_and_throw<F: Fn(u64) -> u64>(&mut self, relief_fn: F) -> Result<(u64, usize), bool>;
@@ -31,7 +33,7 @@ trait Player {
 
 impl Player for Monkey {
     fn inspect_and_throw<F: Fn(u64) -> u64>(&mut self, relief_fn: F) -> Result<(u64, usize), bool> {
-        if self.items.len() == 0 {
+        if self.items.is_empty() {
             return Err(true);
         }
 
@@ -47,7 +49,7 @@ impl Player for Monkey {
             },
         };
         item = relief_fn(item);
-        let throw_to = match item % self.test.divisible_by == 0 {
+        let throw_to = match item.is_multiple_of(self.test.divisible_by) {
             true => self.test.true_monkey,
             false => self.test.false_monkey,
         };
@@ -57,8 +59,8 @@ impl Player for Monkey {
 }
 
 #[derive(Clone)]
-struct KeepAwayGame {
-    monkeys: Jungle,
+pub struct KeepAwayGame {
+    pub monkeys: Jungle,
 }
 
 impl KeepAwayGame {
@@ -75,76 +77,105 @@ impl KeepAwayGame {
         }
     }
 
-    fn start_with_relief_coefficient(&mut self, rounds: usize) {
+    pub fn start_with_relief_coefficient(&mut self, rounds: usize) {
         self.start(rounds, |item| item / 3)
     }
 
-    fn start_without_relief_coefficient(&mut self, rounds: usize) {
+    pub fn start_without_relief_coefficient(&mut self, rounds: usize) {
         let least_common_multiplier: u64 =
             self.monkeys.iter().map(|m| m.test.divisible_by).product();
         self.start(rounds, |item| item % least_common_multiplier);
     }
 }
 
-fn parser(input: &str) -> Jungle {
+/// Monkey the attribute lines below a `Monkey N:` header belong to
+fn current<'m>(monkeys: &'m mut Jungle, line: &Line) -> Result<&'m mut Monkey, ParseError> {
+    monkeys
+        .last_mut()
+        .ok_or_else(|| line.error(line.text, "a `Monkey N:` header"))
+}
+
+/// Last space separated token of the line
+fn last_token<'a>(line: &Line<'a>) -> &'a str {
+    line.text.rsplit(' ').next().unwrap_or(line.text)
+}
+
+pub fn parser(input: &str) -> Result<Jungle, ParseError> {
     let mut monkeys: Jungle = Vec::new();
+    let mut headers: Vec<Line> = Vec::new();
+    let mut throws: Vec<(Line, &str)> = Vec::new();
     let mut idx: usize = 0;
-    for line in input.lines() {
+    for l in lines(11, input) {
+        let line = l.text;
         if line.starts_with("Monkey") {
             monkeys.push(Monkey {
-                idx: idx,
+                idx,
                 ..Monkey::default()
             });
+            headers.push(l);
             idx += 1;
         }
         if line.starts_with("  Starting items:") {
-            let items: Vec<u64> = line
-                .split(": ")
-                .nth(1)
-                .unwrap()
+            let items: Vec<u64> = l
+                .split_once(line, ": ")?
+                .1
                 .split(", ")
-                .map(|s| s.parse::<u64>().unwrap())
-                .collect();
-            monkeys.last_mut().unwrap().items = items;
+                .map(|s| l.parse::<u64>(s, "a worry level"))
+                .collect::<Result<Vec<u64>, ParseError>>()?;
+            current(&mut monkeys, &l)?.items = items;
         }
         if line.starts_with("  Operation:") {
-            let operation_symbol = line
-                .split(": new = old ")
-                .nth(1)
-                .unwrap()
-                .chars()
-                .take(1)
-                .collect::<String>();
-            match operation_symbol.as_str() {
-                "*" => {
-                    monkeys.last_mut().unwrap().operation_type = Some(OperationType::Multiplication)
-                }
-                "+" => monkeys.last_mut().unwrap().operation_type = Some(OperationType::Addition),
-                _ => panic!("Unknown operation symbol"),
-            }
-            let operation_number = line.split(" ").last().unwrap().parse::<u64>();
-            monkeys.last_mut().unwrap().operation_number = match operation_number {
-                Ok(n) => Some(n),
-                Err(_) => None,
+            let operation = l.split_once(line, ": new = old ")?.1;
+            let monkey = current(&mut monkeys, &l)?;
+            match operation.chars().next() {
+                Some('*') => monkey.operation_type = Some(OperationType::Multiplication),
+                Some('+') => monkey.operation_type = Some(OperationType::Addition),
+                _ => return Err(l.error(operation, "`*` or `+`")),
             }
+            monkey.operation_number = match last_token(&l) {
+                "old" => None,
+                number => Some(l.parse::<u64>(number, "a number or `old`")?),
+            };
         }
         if line.starts_with("  Test:") {
-            let divisible_by_number = line.split(" ").last().unwrap().parse::<u64>().unwrap();
-            monkeys.last_mut().unwrap().test.divisible_by = divisible_by_number;
+            let divisible_by_number = l.parse::<u64>(last_token(&l), "a divisor")?;
+            if divisible_by_number == 0 {
+                return Err(l.error(last_token(&l), "a divisor greater than 0"));
+            }
+            current(&mut monkeys, &l)?.test.divisible_by = divisible_by_number;
         }
         if line.starts_with("    If true:") {
-            let monkey = line.split(" ").last().unwrap().parse::<u64>().unwrap();
-            monkeys.last_mut().unwrap().test.true_monkey = monkey as usize;
+            let monkey = l.parse::<u64>(last_token(&l), "a monkey number")?;
+            current(&mut monkeys, &l)?.test.true_monkey = monkey as usize;
+            throws.push((l, last_token(&l)));
         }
         if line.starts_with("    If false:") {
-            let monkey = line.split(" ").last().unwrap().parse::<u64>().unwrap();
-            monkeys.last_mut().unwrap().test.false_monkey = monkey as usize;
+            let monkey = l.parse::<u64>(last_token(&l), "a monkey number")?;
+            current(&mut monkeys, &l)?.test.false_monkey = monkey as usize;
+            throws.push((l, last_token(&l)));
         }
     }
-    monkeys
+
+    for (monkey, header) in monkeys.iter().zip(&headers) {
+        let missing = if monkey.operation_type.is_none() {
+            "an `Operation:` line"
+        } else if monkey.test.divisible_by == 0 {
+            "a `Test:` line"
+        } else {
+            continue;
+        };
+        return Err(header.error(header.text, &format!("a monkey with {missing}")));
+    }
+    for (l, token) in throws {
+        if token.parse::<usize>().unwrap() >= monkeys.len() {
+            let expected = format!("a monkey between 0 and {}", monkeys.len() - 1);
+            return Err(l.error(token, &expected));
+        }
+    }
+    Ok(monkeys)
 }
 
-fn calculate_monkey_business(game: &KeepAwayGame) -> u64 {
+pub fn calculate_monkey_business(game: &KeepAwayGame) -> u64 {
     let mut monkey_inspections = game
         .monkeys
         .iter()
@@ -160,23 +191,34 @@ fn calculate_monkey_business(game: &KeepAwayGame) -> u64 {
     top_two.iter().product()
 }
 
-pub fn solution() -> (String, String) {
-    let contents = read_file("/inputs/day11.txt");
+pub struct Day11;
 
-    let mut game = KeepAwayGame {
-        monkeys: parser(&contents),
-    };
-    let mut game2 = game.clone();
+impl Solution for Day11 {
+    type Input = Jungle;
 
-    // Part 1
-    game.start_with_relief_coefficient(20);
-    let result1: u64 = calculate_monkey_business(&game);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parser(input)
+    }
 
-    // Part 2
-    game2.start_without_relief_coefficient(10000);
-    let result2: u64 = calculate_monkey_business(&game2);
+    fn part1(input: &Self::Input) -> String {
+        let mut game = KeepAwayGame {
+            monkeys: input.clone(),
+        };
+        game.start_with_relief_coefficient(20);
+        let result1: u64 = calculate_monkey_business(&game);
+
+        return result1.to_string();
+    }
 
-    return (result1.to_string(), result2.to_string());
+    fn part2(input: &Self::Input) -> String {
+        let mut game = KeepAwayGame {
+            monkeys: input.clone(),
+        };
+        game.start_without_relief_coefficient(10000);
+        let result2: u64 = calculate_monkey_business(&game);
+
+        return result2.to_string();
+    }
 }
 
 #[cfg(test)]
@@ -214,7 +256,7 @@ Monkey 3:
 
     #[test]
     fn parser() {
-        let jungle = day11::parser(TEST_INPUT);
+        let jungle = day11::parser(TEST_INPUT).unwrap();
 
         assert_eq!(jungle.len(), 4);
         assert_eq!(jungle[0].items, vec![79, 98]);
@@ -235,7 +277,7 @@ Monkey 3:
 
     #[test]
     fn play_twenty_rounds() {
-        let jungle = day11::parser(TEST_INPUT);
+        let jungle = day11::parser(TEST_INPUT).unwrap();
         let game = &mut day11::KeepAwayGame { monkeys: jungle };
 
         println!("start: {:?}", game.monkeys);
@@ -251,4 +293,26 @@ Monkey 3:
         assert_eq!(game.monkeys[2].inspections, 7);
         assert_eq!(game.monkeys[3].inspections, 105);
     }
+
+    #[test]
+    fn parser_errors() {
+        let error = day11::parser(&TEST_INPUT.replace("old * 19", "old / 19")).unwrap_err();
+        assert_eq!((error.line, error.column), (3, 24));
+        assert_eq!(error.found, "`/ 19`");
+
+        let error = day11::parser(&TEST_INPUT.replace("79, 98", "79, x")).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 23));
+        assert_eq!(error.expected, "a worry level");
+
+        let error = day11::parser(&TEST_INPUT.replace("throw to monkey 3", "throw to monkey 4")).unwrap_err();
+        assert_eq!(error.line, 6);
+        assert_eq!(error.expected, "a monkey between 0 and 3");
+
+        let error = day11::parser(&TEST_INPUT.replace("  Test: divisible by 13\n", "")).unwrap_err();
+        assert_eq!(error.line, 15);
+        assert_eq!(error.expected, "a monkey with a `Test:` line");
+
+        let error = day11::parser("  Starting items: 79").unwrap_err();
+        assert_eq!(error.expected, "a `Monkey N:` header");
+    }
 }
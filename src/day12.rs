@@ -1,130 +1,288 @@
-use crate::helpers::read_file;
+use crate::error::ParseError;
+use crate::helpers::{ansi, search, Direction8, Grid, Point2};
+use crate::solution::Solution;
 use once_cell::sync::Lazy;
 
-use std::{
-    cmp::Reverse,
-    collections::{BinaryHeap, HashMap},
-};
+use std::collections::HashMap;
 
-type Grid = Vec<Vec<char>>;
-type Pos = (usize, usize);
+/// Elevations `a` to `z`, plus the start `S` and the destination `E`
+pub type Heightmap = Grid<char>;
+pub type Pos = Point2;
+/// Cost from each square to a destination, or from a start, indexed like the heightmap
+pub type DistanceMap = Grid<Option<usize>>;
 
 static ALPHABET: Lazy<HashMap<char, u32>> =
     Lazy::new(|| HashMap::from_iter((10..36).map(|n| (char::from_digit(n, 36).unwrap(), n))));
 
-/// Returns (Start, End, Grid) tuple
-fn parser(input: &str) -> (Pos, Pos, Grid) {
-    let mut start: Pos = (0, 0);
-    let mut end: Pos = (0, 0);
-    let grid = input
-        .lines()
-        .enumerate()
-        .map(|(y, row)| {
-            row.chars()
-                .enumerate()
-                .map(|(x, c)| {
-                    if c.to_string() == "S" {
-                        start = (y, x)
-                    }
-                    if c.to_string() == "E" {
-                        end = (y, x)
-                    }
-                    c
-                })
-                .collect()
-        })
-        .collect();
-    (start, end, grid)
-}
-
-fn get_low_points(grid: &Grid) -> Vec<Pos> {
-    let mut result = Vec::new();
-    grid.iter().enumerate().for_each(|(y, line)| {
-        line.iter().enumerate().for_each(|(x, char)| {
-            if char.to_string() == "a" {
-                result.push((y, x))
-            }
-        })
-    });
-    result
+/// Returns (Start, End, Heightmap) tuple
+pub fn parser(input: &str) -> Result<(Pos, Pos, Heightmap), ParseError> {
+    let grid: Heightmap = Grid::parse(12, input, "squares", |line, square| {
+        match square.chars().next().unwrap() {
+            c @ ('S' | 'E' | 'a'..='z') => Ok(c),
+            _ => Err(line.error(square, "an elevation from `a` to `z`")),
+        }
+    })?;
+    let start = grid
+        .position(|&c| c == 'S')
+        .ok_or_else(|| ParseError::end_of_input(12, input, "a start `S`"))?;
+    let end = grid
+        .position(|&c| c == 'E')
+        .ok_or_else(|| ParseError::end_of_input(12, input, "a destination `E`"))?;
+    Ok((Point2::from_index(start), Point2::from_index(end), grid))
 }
 
-/// Returns list of neighbors of a given position
-fn neighbors(grid: &Grid, pos: &Pos) -> Vec<Pos> {
-    let mut result = Vec::new();
-    if pos.0 > 0 {
-        result.push((pos.0 - 1, pos.1));
-    }
-    if pos.1 > 0 {
-        result.push((pos.0, pos.1 - 1));
+pub fn get_low_points(grid: &Heightmap) -> Vec<Pos> {
+    grid.positions()
+        .filter(|pos| grid[*pos] == 'a')
+        .map(Point2::from_index)
+        .collect()
+}
+
+/// Elevation of a square, from 0 for `a` and `S` to 25 for `z` and `E`
+pub fn elevation(square: char) -> u32 {
+    let letter = match square {
+        'S' => 'a',
+        'E' => 'z',
+        c => c,
+    };
+    ALPHABET[&letter] - ALPHABET[&'a']
+}
+
+pub fn height_diff(grid: &Heightmap, curr: &Pos, next: &Pos) -> i32 {
+    elevation(grid[*next]) as i32 - elevation(grid[*curr]) as i32
+}
+
+/// Price of a step, from the height difference between the squares (positive when climbing)
+#[derive(Debug, Clone, Copy)]
+pub enum StepCost {
+    /// Every step costs 1, as in the puzzle
+    Unit,
+    /// Any price computed from the height difference, for example to make climbs expensive
+    Height(fn(i32) -> usize),
+}
+
+/// Which steps a hiker may take on the heightmap and what they cost
+#[derive(Debug, Clone, Copy)]
+pub struct ClimbingRules {
+    /// Most levels climbed in one step
+    pub max_climb: u32,
+    /// Most levels descended in one step, `None` for no limit
+    pub max_descent: Option<u32>,
+    /// Also step to the 4 diagonal neighbors
+    pub diagonal: bool,
+    pub cost: StepCost,
+}
+
+impl ClimbingRules {
+    /// Climb at most one level, descend any height, 4 directions, every step costs 1
+    pub const PUZZLE: ClimbingRules = ClimbingRules {
+        max_climb: 1,
+        max_descent: None,
+        diagonal: false,
+        cost: StepCost::Unit,
+    };
+
+    /// Cost of a step changing the height by `diff`, `None` if the rules forbid it
+    pub fn step_cost(&self, diff: i32) -> Option<usize> {
+        let allowed =
+            diff <= self.max_climb as i32 && self.max_descent.is_none_or(|max| -diff <= max as i32);
+        if !allowed {
+            return None;
+        }
+        match self.cost {
+            StepCost::Unit => Some(1),
+            StepCost::Height(cost) => Some(cost(diff)),
+        }
     }
-    if pos.0 + 1 < grid.len() {
-        result.push((pos.0 + 1, pos.1));
+
+    fn directions(&self) -> impl Iterator<Item = Direction8> + '_ {
+        Direction8::ALL
+            .into_iter()
+            .filter(|direction| self.diagonal || !direction.is_diagonal())
     }
-    if pos.1 + 1 < grid[0].len() {
-        result.push((pos.0, pos.1 + 1));
+}
+
+impl Default for ClimbingRules {
+    fn default() -> ClimbingRules {
+        ClimbingRules::PUZZLE
     }
-    result.iter().cloned().collect()
 }
 
-fn height_diff(grid: &Grid, curr: &Pos, next: &Pos) -> i32 {
-    let curr_elevation;
-    let next_elevation;
-    match grid[curr.0][curr.1] {
-        'S' => curr_elevation = 'a',
-        'E' => curr_elevation = 'z',
-        _ => curr_elevation = grid[curr.0][curr.1],
+/// Squares reachable in one step from `pos` under `rules`, with the cost of the step
+pub fn moves<'a>(
+    grid: &'a Heightmap,
+    rules: &'a ClimbingRules,
+    pos: Pos,
+) -> impl Iterator<Item = (Pos, usize)> + 'a {
+    rules.directions().filter_map(move |direction| {
+        let next = pos + direction.delta();
+        grid.get_point(next)?;
+        Some((next, rules.step_cost(height_diff(grid, &pos, &next))?))
+    })
+}
+
+/// Squares from which `pos` is reachable in one step, with the cost of the step, the inverse of `moves`
+pub fn reverse_moves<'a>(
+    grid: &'a Heightmap,
+    rules: &'a ClimbingRules,
+    pos: Pos,
+) -> impl Iterator<Item = (Pos, usize)> + 'a {
+    rules.directions().filter_map(move |direction| {
+        let prev = pos + direction.delta();
+        grid.get_point(prev)?;
+        Some((prev, rules.step_cost(height_diff(grid, &prev, &pos))?))
+    })
+}
+
+/// Cheapest way from `start` to `end` with the squares on the way and its cost (the number of
+/// steps under the puzzle rules), `None` if `end` can't be reached
+pub fn shortest_path(
+    grid: &Heightmap,
+    rules: &ClimbingRules,
+    start: &Pos,
+    end: &Pos,
+) -> Option<(Vec<Pos>, usize)> {
+    match rules.cost {
+        StepCost::Unit => search::bfs(
+            *start,
+            |pos| moves(grid, rules, *pos).map(|(next, _)| next),
+            |pos| pos == end,
+        ),
+        StepCost::Height(_) => {
+            search::dijkstra(*start, |pos| moves(grid, rules, *pos), |pos| pos == end)
+        }
     }
-    match grid[next.0][next.1] {
-        'S' => next_elevation = 'a',
-        'E' => next_elevation = 'z',
-        _ => next_elevation = grid[next.0][next.1],
+}
+
+/// Costs found by a search over the whole heightmap, put on a grid
+fn distance_map(grid: &Heightmap, distances: HashMap<Pos, usize>) -> DistanceMap {
+    let mut map = Grid::new(grid.height(), grid.width(), None);
+    for (pos, cost) in distances {
+        map[pos] = Some(cost);
     }
-    ALPHABET[&next_elevation] as i32 - ALPHABET[&curr_elevation] as i32
+    map
 }
 
-fn dijkstra(grid: &Grid, start: &Pos, end: &Pos) -> usize {
-    let mut visited = HashMap::new();
-    let mut heap = BinaryHeap::new();
-    heap.push(Reverse((0, *start)));
-    while let Some(Reverse((steps, curr))) = heap.pop() {
-        if &curr == end {
-            return steps;
+/// Cost from every square to `end`, `None` where `end` can't be reached.
+/// One search backwards from `end` fills it, so it's cheap to query many starts.
+pub fn distances_to(grid: &Heightmap, rules: &ClimbingRules, end: &Pos) -> DistanceMap {
+    let distances = match rules.cost {
+        StepCost::Unit => search::bfs_distances(*end, |pos| {
+            reverse_moves(grid, rules, *pos).map(|(prev, _)| prev)
+        }),
+        StepCost::Height(_) => {
+            search::dijkstra_distances(*end, |pos| reverse_moves(grid, rules, *pos))
         }
-        if visited.contains_key(&curr) {
-            continue;
+    };
+    distance_map(grid, distances)
+}
+
+/// Cost from `start` to every square, `None` where the square can't be reached
+pub fn distances_from(grid: &Heightmap, rules: &ClimbingRules, start: &Pos) -> DistanceMap {
+    let distances = match rules.cost {
+        StepCost::Unit => {
+            search::bfs_distances(*start, |pos| moves(grid, rules, *pos).map(|(next, _)| next))
         }
-        visited.insert(curr, steps);
-        // println!("{}: {:?} {:?}", steps, curr, grid[curr.0][curr.1]);
-        for next in neighbors(&grid, &curr) {
-            if height_diff(&grid, &curr, &next) <= 1 {
-                heap.push(Reverse((steps + 1, next)));
+        StepCost::Height(_) => search::dijkstra_distances(*start, |pos| moves(grid, rules, *pos)),
+    };
+    distance_map(grid, distances)
+}
+
+/// Background of the squares drawn by `render_path`
+pub enum Coloring<'a> {
+    Plain,
+    /// Darker squares are lower
+    Elevation,
+    /// Darker squares are closer, squares missing from the map are left uncolored
+    Distance(&'a DistanceMap),
+}
+
+/// Draws `path` over the heightmap like the puzzle does: every square of the path shows an arrow
+/// to the next one (diagonal arrows for diagonal moves), the last square keeps its letter and the other squares are `.`
+pub fn render_path(grid: &Heightmap, path: &[Pos], coloring: Coloring) -> String {
+    let mut canvas: Grid<char> = Grid::new(grid.height(), grid.width(), '.');
+    for step in path.windows(2) {
+        let direction = Direction8::from_delta(step[1] - step[0])
+            .expect("path squares must be next to each other");
+        canvas[step[0]] = direction.arrow();
+    }
+    if let Some(last) = path.last() {
+        canvas[*last] = grid[*last];
+    }
+
+    let max_distance = match &coloring {
+        Coloring::Distance(distances) => distances
+            .iter()
+            .flatten()
+            .max()
+            .copied()
+            .unwrap_or(0)
+            .max(1),
+        _ => 1,
+    };
+    let level = |pos: Pos| -> Option<f64> {
+        match &coloring {
+            Coloring::Plain => None,
+            Coloring::Elevation => Some(elevation(grid[pos]) as f64 / 25.0),
+            Coloring::Distance(distances) => {
+                distances[pos].map(|steps| steps as f64 / max_distance as f64)
             }
         }
+    };
+    let mut lines = Vec::new();
+    for row in 0..canvas.height() {
+        let line: String = canvas
+            .row(row)
+            .iter()
+            .enumerate()
+            .map(|(col, c)| match level(Point2::from_index((row, col))) {
+                Some(level) => ansi::shade(&c.to_string(), level),
+                None => c.to_string(),
+            })
+            .collect();
+        lines.push(line);
     }
-    usize::MAX
+    lines.join("\n")
 }
 
-pub fn solution() -> (String, String) {
-    let contents = read_file("/inputs/day12.txt");
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = (Pos, Pos, Heightmap);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parser(input)
+    }
+
+    fn part1(input: &Self::Input) -> String {
+        let (start, end, grid) = input;
+
+        let (_, result1) =
+            shortest_path(grid, &ClimbingRules::PUZZLE, start, end).expect("no path from S to E");
 
-    let (start, end, grid) = parser(&contents);
+        return result1.to_string();
+    }
 
-    let result1: usize = dijkstra(&grid, &start, &end);
+    fn part2(input: &Self::Input) -> String {
+        let (_, end, grid) = input;
 
-    let low_points = get_low_points(&grid);
-    let result2: usize = low_points
-        .iter()
-        .map(|start_pos| dijkstra(&grid, &start_pos, &end))
-        .min()
-        .unwrap();
+        let distances = distances_to(grid, &ClimbingRules::PUZZLE, end);
+        let result2: usize = get_low_points(grid)
+            .iter()
+            .filter_map(|start_pos| distances[*start_pos])
+            .min()
+            .expect("no path from an `a` square to E");
 
-    return (result1.to_string(), result2.to_string());
+        return result2.to_string();
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day12;
+    use crate::day12::{self, ClimbingRules, Coloring, StepCost};
+    use crate::helpers::{ansi, Point2};
+
+    const PUZZLE: ClimbingRules = ClimbingRules::PUZZLE;
 
     const TEST_INPUT: &str = "\
 Sabqponm
@@ -135,21 +293,21 @@ abdefghi";
 
     #[test]
     fn parser() {
-        let (start, end, grid) = day12::parser(TEST_INPUT);
-        assert_eq!(start, (0, 0));
-        assert_eq!(end, (2, 5));
-        assert_eq!(grid.len(), 5);
-        assert_eq!(grid[0].len(), 8);
-        assert_eq!(grid[0][0], 'S');
-        assert_eq!(grid[2][1], 'c');
-        assert_eq!(grid[2][1], 'c');
-        assert_eq!(grid[2][2], 'c');
-        assert_eq!(grid[2][3], 's');
-        assert_eq!(grid[2][4], 'z');
-        assert_eq!(grid[2][5], 'E');
-        assert_eq!(grid[2][7], 'k');
-        assert_eq!(grid[3][7], 'j');
-        assert_eq!(grid[4][7], 'i');
+        let (start, end, grid) = day12::parser(TEST_INPUT).unwrap();
+        assert_eq!(start, Point2::new(0, 0));
+        assert_eq!(end, Point2::new(5, 2));
+        assert_eq!(grid.height(), 5);
+        assert_eq!(grid.width(), 8);
+        assert_eq!(grid[(0, 0)], 'S');
+        assert_eq!(grid[(2, 1)], 'c');
+        assert_eq!(grid[(2, 1)], 'c');
+        assert_eq!(grid[(2, 2)], 'c');
+        assert_eq!(grid[(2, 3)], 's');
+        assert_eq!(grid[(2, 4)], 'z');
+        assert_eq!(grid[(2, 5)], 'E');
+        assert_eq!(grid[(2, 7)], 'k');
+        assert_eq!(grid[(3, 7)], 'j');
+        assert_eq!(grid[(4, 7)], 'i');
     }
 
     #[test]
@@ -164,30 +322,35 @@ abdefghi";
 
     #[test]
     fn neighbors() {
-        let (start, _, grid) = day12::parser(TEST_INPUT);
-        assert_eq!(day12::neighbors(&grid, &start), vec![(1, 0), (0, 1)]);
+        let (_, _, grid) = day12::parser(TEST_INPUT).unwrap();
+        assert_eq!(
+            grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0)]
+        );
         assert_eq!(
-            day12::neighbors(&grid, &(0, 1)),
-            vec![(0, 0), (1, 1), (0, 2)]
+            grid.neighbors4((0, 1)).collect::<Vec<_>>(),
+            vec![(0, 2), (1, 1), (0, 0)]
         );
         assert_eq!(
-            day12::neighbors(&grid, &(3, 3)),
-            vec![(2, 3), (3, 2), (4, 3), (3, 4)]
+            grid.neighbors4((3, 3)).collect::<Vec<_>>(),
+            vec![(2, 3), (3, 4), (4, 3), (3, 2)]
         );
     }
 
     #[test]
     fn height_diff() {
-        let (start, end, grid) = day12::parser(TEST_INPUT);
-        assert_eq!(day12::height_diff(&grid, &start, &(1, 0)), 0);
-        assert_eq!(day12::height_diff(&grid, &start, &(0, 1)), 0);
-        assert_eq!(day12::height_diff(&grid, &(1, 5), &end), 2);
+        let (start, end, grid) = day12::parser(TEST_INPUT).unwrap();
+        assert_eq!(day12::height_diff(&grid, &start, &Point2::new(0, 1)), 0);
+        assert_eq!(day12::height_diff(&grid, &start, &Point2::new(1, 0)), 0);
+        assert_eq!(day12::height_diff(&grid, &Point2::new(5, 1), &end), 2);
     }
 
     #[test]
-    fn dijkstra() {
-        let (start, end, grid) = day12::parser(TEST_INPUT);
-        assert_eq!(day12::dijkstra(&grid, &start, &end), 31);
+    fn shortest_path() {
+        let (start, end, grid) = day12::parser(TEST_INPUT).unwrap();
+        let (path, steps) = day12::shortest_path(&grid, &PUZZLE, &start, &end).unwrap();
+        assert_eq!(steps, 31);
+        assert_eq!((path[0], path[31]), (start, end));
     }
 
     const TEST_INPUT_TWO: &str = "\
@@ -197,17 +360,151 @@ abcdefgh
 accczzxk";
 
     #[test]
-    fn dijkstra_two() {
-        let (start, end, grid) = day12::parser(TEST_INPUT_TWO);
-        assert_eq!(day12::dijkstra(&grid, &start, &end), 0);
+    fn shortest_path_two() {
+        // there is no `y` square, so no `z` square and no E can ever be reached
+        let (start, end, grid) = day12::parser(TEST_INPUT_TWO).unwrap();
+        assert_eq!(day12::shortest_path(&grid, &PUZZLE, &start, &end), None);
+    }
+
+    #[test]
+    fn distances_to() {
+        let (start, end, grid) = day12::parser(TEST_INPUT).unwrap();
+        let distances = day12::distances_to(&grid, &PUZZLE, &end);
+        assert_eq!(distances[start], Some(31));
+        assert_eq!(distances[end], Some(0));
+        assert_eq!(distances[Point2::new(0, 4)], Some(29));
+        for pos in grid.positions().map(Point2::from_index) {
+            let steps = day12::shortest_path(&grid, &PUZZLE, &pos, &end).map(|(_, steps)| steps);
+            assert_eq!(distances[pos], steps);
+        }
+
+        let (start, end, grid) = day12::parser(TEST_INPUT_TWO).unwrap();
+        assert_eq!(day12::distances_to(&grid, &PUZZLE, &end)[start], None);
+    }
+
+    #[test]
+    fn render_path() {
+        let (start, end, grid) = day12::parser(TEST_INPUT).unwrap();
+        let (path, _) = day12::shortest_path(&grid, &PUZZLE, &start, &end).unwrap();
+        // as short as the route drawn in the puzzle, with a different start
+        assert_eq!(
+            day12::render_path(&grid, &path, Coloring::Plain),
+            "\
+>>vv<<<<
+..vvv<<^
+..vv>E^^
+..v>>>^^
+..>>>>>^"
+        );
+
+        let colored = day12::render_path(&grid, &path, Coloring::Elevation);
+        assert!(colored.starts_with(&ansi::shade(">", 0.0)));
+        assert_eq!(colored.lines().count(), 5);
+
+        // S is walled in by `z` squares, only the top row leads to E
+        let (start, end, grid) = day12::parser(TEST_INPUT_TWO).unwrap();
+        let distances = day12::distances_from(&grid, &PUZZLE, &start);
+        assert_eq!(distances.iter().flatten().count(), 1);
+        let distances = day12::distances_to(&grid, &PUZZLE, &end);
+        let colored = day12::render_path(&grid, &[], Coloring::Distance(&distances));
+        let first_line = colored.lines().next().unwrap();
+        assert!(first_line.starts_with(&format!(".{}", ansi::shade(".", 1.0))));
+        assert!(colored.ends_with("\n........"));
+    }
+
+    #[test]
+    fn climbing_rules() {
+        let (start, end, grid) = day12::parser(TEST_INPUT).unwrap();
+
+        // a two level climb reaches E from the `x` next to it
+        let rules = ClimbingRules {
+            max_climb: 2,
+            ..PUZZLE
+        };
+        let (_, steps) = day12::shortest_path(&grid, &rules, &start, &end).unwrap();
+        assert_eq!(steps, 27);
+
+        // the spiral of the example never goes down, but it has to climb
+        let rules = ClimbingRules {
+            max_descent: Some(0),
+            ..PUZZLE
+        };
+        assert_eq!(
+            day12::shortest_path(&grid, &rules, &start, &end).unwrap().1,
+            31
+        );
+        let rules = ClimbingRules {
+            max_climb: 0,
+            ..PUZZLE
+        };
+        assert_eq!(day12::shortest_path(&grid, &rules, &start, &end), None);
+
+        let rules = ClimbingRules {
+            diagonal: true,
+            ..PUZZLE
+        };
+        let (path, steps) = day12::shortest_path(&grid, &rules, &start, &end).unwrap();
+        assert!(steps < 31);
+        assert!(path.windows(2).any(|step| step[0].manhattan(step[1]) == 2));
+        assert!(day12::render_path(&grid, &path, Coloring::Plain).contains(['↗', '↘', '↙', '↖']));
+
+        let distances = day12::distances_to(&grid, &rules, &end);
+        assert_eq!(distances[start], Some(steps));
+    }
+
+    #[test]
+    fn step_costs() {
+        let (start, end, grid) = day12::parser(TEST_INPUT).unwrap();
+        let expensive_climbs = ClimbingRules {
+            cost: StepCost::Height(|diff| 1 + 10 * diff.max(0) as usize),
+            ..PUZZLE
+        };
+        assert_eq!(expensive_climbs.step_cost(1), Some(11));
+        assert_eq!(expensive_climbs.step_cost(-3), Some(1));
+        assert_eq!(expensive_climbs.step_cost(2), None);
+
+        // 25 climbs of one level are needed whatever the route, so only the step count varies
+        let (path, cost) = day12::shortest_path(&grid, &expensive_climbs, &start, &end).unwrap();
+        assert_eq!(cost, 31 + 250);
+        assert_eq!(path.len(), 32);
+        let distances = day12::distances_to(&grid, &expensive_climbs, &end);
+        assert_eq!(distances[start], Some(cost));
+        assert_eq!(
+            day12::distances_from(&grid, &expensive_climbs, &start)[end],
+            Some(cost)
+        );
+
+        // with free descents and unit climbs, dijkstra and bfs agree
+        let unit = ClimbingRules {
+            cost: StepCost::Height(|_| 1),
+            ..PUZZLE
+        };
+        assert_eq!(
+            day12::distances_to(&grid, &unit, &end),
+            day12::distances_to(&grid, &PUZZLE, &end)
+        );
     }
 
     #[test]
     fn get_low_points() {
-        let (_, _, grid) = day12::parser(TEST_INPUT);
+        let (_, _, grid) = day12::parser(TEST_INPUT).unwrap();
         assert_eq!(
             day12::get_low_points(&grid),
-            vec![(0, 1), (1, 0), (2, 0), (3, 0), (4, 0)]
+            [(0, 1), (1, 0), (2, 0), (3, 0), (4, 0)].map(Point2::from_index)
         );
     }
+
+    #[test]
+    fn parser_errors() {
+        let error = day12::parser("Sab\naBc\nabE").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.found, "`B`");
+
+        let error = day12::parser("Sab\nab\nabE").unwrap_err();
+        assert_eq!(error.expected, "a row of 3 squares");
+
+        let error = day12::parser("Sab\nabc").unwrap_err();
+        assert_eq!((error.line, error.found.as_str()), (3, "end of input"));
+        assert_eq!(error.expected, "a destination `E`");
+    }
 }
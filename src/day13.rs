@@ -1,48 +1,178 @@
-use crate::helpers::read_file;
+use crate::error::{lines, Line, ParseError};
+use crate::solution::Solution;
+use std::cmp::Ordering;
+use std::slice;
 
-#[derive(PartialEq, Eq, Debug)]
-enum PacketData {
-    Num(u8),
+/// Packets compare with the puzzle rules: numbers by value, lists element by element and then by
+/// length, and a number against a list as if it were a list of that one number. Equality follows
+/// the same rules, so `1` equals `[1]`.
+#[derive(Debug, Clone)]
+pub enum PacketData {
+    Num(u32),
     List(Vec<PacketData>),
 }
 
-type PacketDataInput = Vec<(Vec<PacketData>, Vec<PacketData>)>;
+impl Ord for PacketData {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (PacketData::Num(a), PacketData::Num(b)) => a.cmp(b),
+            (PacketData::List(a), PacketData::List(b)) => a.cmp(b),
+            (PacketData::Num(_), PacketData::List(b)) => slice::from_ref(self).cmp(b),
+            (PacketData::List(a), PacketData::Num(_)) => a.as_slice().cmp(slice::from_ref(other)),
+        }
+    }
+}
 
-fn parse_packet_data(s: &str) -> Vec<PacketData> {
-    // approaches: recursive and non recursive solutions
-    // if s.starts_with('[') {
-    //     parse_packet_data(s.trim_start_matches('['), 1)
-    // }
-    // parse_packet_data(s.trim_start_matches('['), 1)
-    // s.chars().take_while(predicate)
-    Vec::new()
+impl PartialOrd for PacketData {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
-fn parser(input: &str) -> PacketDataInput {
-    input
-        .trim()
-        .split("\n\n")
-        .map(|pair| {
-            let mut lines = pair.lines();
-            let left = lines.next().unwrap();
-            let right = lines.next().unwrap();
-
-            (parse_packet_data(left), parse_packet_data(right))
-        })
-        .collect::<PacketDataInput>()
+impl PartialEq for PacketData {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
 }
 
-pub fn solution() -> (String, String) {
-    let contents = read_file("/inputs/day13.txt");
+impl Eq for PacketData {}
 
-    let result1: usize = 0;
-    let result2: usize = 0;
-    return (result1.to_string(), result2.to_string());
+/// A packet is the content of its outermost list, so packets compare as `Vec<PacketData>`
+pub type Packet = Vec<PacketData>;
+
+pub type PacketDataInput = Vec<(Packet, Packet)>;
+
+/// Recursive descent over the packet on `line`, `rest` being the part not read yet
+struct PacketParser<'a> {
+    line: Line<'a>,
+    rest: &'a str,
+}
+
+impl<'a> PacketParser<'a> {
+    fn next_char(&self) -> &'a str {
+        let len = self.rest.chars().next().map_or(0, char::len_utf8);
+        &self.rest[..len]
+    }
+
+    fn expect(&mut self, token: &str, expected: &str) -> Result<(), ParseError> {
+        match self.rest.strip_prefix(token) {
+            Some(rest) => {
+                self.rest = rest;
+                Ok(())
+            }
+            None => Err(self.line.error(self.next_char(), expected)),
+        }
+    }
+
+    fn list(&mut self) -> Result<Packet, ParseError> {
+        self.expect("[", "`[`")?;
+        let mut list = Packet::new();
+        if self.rest.starts_with(']') {
+            self.rest = &self.rest[1..];
+            return Ok(list);
+        }
+        loop {
+            list.push(self.value()?);
+            match self.next_char() {
+                "," => self.rest = &self.rest[1..],
+                "]" => {
+                    self.rest = &self.rest[1..];
+                    return Ok(list);
+                }
+                token => return Err(self.line.error(token, "`,` or `]`")),
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<PacketData, ParseError> {
+        if self.rest.starts_with('[') {
+            return Ok(PacketData::List(self.list()?));
+        }
+        let digits = self.rest.len() - self.rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        if digits == 0 {
+            return Err(self.line.error(self.next_char(), "a number or a list"));
+        }
+        let (number, rest) = self.rest.split_at(digits);
+        self.rest = rest;
+        Ok(PacketData::Num(self.line.parse(number, "a number")?))
+    }
+}
+
+/// Parses the packet on `line` into the items of its outermost list
+pub fn parse_packet_data(line: &Line) -> Result<Packet, ParseError> {
+    let text = line.text.trim_end();
+    let mut parser = PacketParser { line: *line, rest: text };
+    let packet = parser.list()?;
+    if !parser.rest.is_empty() {
+        return Err(line.error(parser.rest, "the end of the packet"));
+    }
+    Ok(packet)
+}
+
+/// The `[[2]]` and `[[6]]` divider packets of part 2
+pub fn divider_packets() -> [Packet; 2] {
+    [2, 6].map(|n| vec![PacketData::List(vec![PacketData::Num(n)])])
+}
+
+pub fn parser(input: &str) -> Result<PacketDataInput, ParseError> {
+    let mut packets = lines(13, input).filter(|l| !l.text.trim().is_empty());
+    let mut pairs = PacketDataInput::new();
+    while let Some(left) = packets.next() {
+        let right = packets
+            .next()
+            .ok_or_else(|| ParseError::end_of_input(13, input, "a right packet"))?;
+
+        pairs.push((parse_packet_data(&left)?, parse_packet_data(&right)?));
+    }
+    Ok(pairs)
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = PacketDataInput;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parser(input)
+    }
+
+    fn part1(input: &Self::Input) -> String {
+        let result1: usize = input
+            .iter()
+            .enumerate()
+            .filter(|(_, (left, right))| left < right)
+            .map(|(idx, _)| idx + 1)
+            .sum();
+
+        return result1.to_string();
+    }
+
+    fn part2(input: &Self::Input) -> String {
+        // Packets can be equal without being the same (`[2]` and `[[2]]`), so the dividers are
+        // tagged rather than searched for; the stable sort keeps them after equal input packets.
+        let dividers = divider_packets();
+        let mut packets: Vec<(&Packet, bool)> = input
+            .iter()
+            .flat_map(|(left, right)| [(left, false), (right, false)])
+            .chain(dividers.iter().map(|divider| (divider, true)))
+            .collect();
+        packets.sort_by_key(|(packet, _)| *packet);
+        let result2: usize = packets
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, is_divider))| *is_divider)
+            .map(|(idx, _)| idx + 1)
+            .product();
+
+        return result2.to_string();
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day13;
+    use crate::day13::{self, Day13, Packet, PacketData};
+    use crate::error;
+    use crate::solution::Solution;
 
     const TEST_INPUT: &str = "
 [1,1,3,1,1]
@@ -70,22 +200,70 @@ mod tests {
 [1,[2,[3,[4,[5,6,0]]]],8,9]
 ";
 
+    fn packet(text: &str) -> Packet {
+        day13::parse_packet_data(&error::lines(13, text).next().unwrap()).unwrap()
+    }
+
     #[test]
     fn parse_packet_data() {
         let input = "[[1],[2,3,4]]";
-        // let t = input.trim().
-        // println!("{:?}", t)
-        // let i = "1]";
-        // let t = i.trim_end_matches("]");
-        // println!("{:?}", t);
-
-        let result = day13::parse_packet_data(input);
-        println!("{:?}", result);
+
+        let result = packet(input);
         assert_eq!(result.len(), 2);
+        assert!(matches!(&result[1], PacketData::List(l) if l.len() == 3));
+        assert!(matches!(packet("[10,[]]")[..], [PacketData::Num(10), PacketData::List(ref l)] if l.is_empty()));
+        assert!(packet("[]").is_empty());
+    }
+
+    #[test]
+    fn parse_packet_data_errors() {
+        let line = error::lines(13, "[1,[2,x]]").next().unwrap();
+        let error = day13::parse_packet_data(&line).unwrap_err();
+        assert_eq!((error.column, error.expected.as_str()), (7, "a number or a list"));
+
+        let line = error::lines(13, "[1,2").next().unwrap();
+        let error = day13::parse_packet_data(&line).unwrap_err();
+        assert_eq!((error.column, error.found.as_str()), (5, "nothing"));
+
+        let line = error::lines(13, "[1]]").next().unwrap();
+        let error = day13::parse_packet_data(&line).unwrap_err();
+        assert_eq!(error.expected, "the end of the packet");
+
+        let error = day13::parser("[1]\n[1 2]").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
     }
+
+    #[test]
+    fn compare_packets() {
+        assert!(packet("[1,1,3,1,1]") < packet("[1,1,5,1,1]"));
+        assert!(packet("[[1],[2,3,4]]") < packet("[[1],4]"));
+        assert!(packet("[9]") > packet("[[8,7,6]]"));
+        assert!(packet("[[4,4],4,4]") < packet("[[4,4],4,4,4]"));
+        assert!(packet("[7,7,7,7]") > packet("[7,7,7]"));
+        assert!(packet("[]") < packet("[3]"));
+        assert!(packet("[[[]]]") > packet("[[]]"));
+        assert!(packet("[1,[2,[3,[4,[5,6,7]]]],8,9]") > packet("[1,[2,[3,[4,[5,6,0]]]],8,9]"));
+        assert!(packet("[10]") > packet("[9]"));
+        assert_eq!(packet("[1]"), packet("[[1]]"));
+    }
+
+    #[test]
+    fn parts() {
+        let input = day13::parser(TEST_INPUT).unwrap();
+        assert_eq!(Day13::part1(&input), "13");
+        assert_eq!(Day13::part2(&input), "140");
+
+        // `[[2]]` in the input equals the first divider but is not it
+        let input = day13::parser("[2]\n[1]\n\n[[2]]\n[3]").unwrap();
+        assert_eq!(Day13::part2(&input), "24");
+    }
+
     #[test]
     fn parser() {
-        let result = day13::parser(TEST_INPUT);
+        let result = day13::parser(TEST_INPUT).unwrap();
         assert_eq!(result.len(), 8);
+
+        let error = day13::parser("[1]\n[2]\n\n[3]").unwrap_err();
+        assert_eq!(error.expected, "a right packet");
     }
 }
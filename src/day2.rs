@@ -1,65 +1,85 @@
-use crate::helpers::read_file;
+use crate::error::{lines, ParseError};
+use crate::solution::Solution;
 use std::collections::HashMap;
 
-fn parser() -> Vec<(String, String)> {
-    let contents = read_file("/inputs/day2.txt");
-
+pub fn parser(contents: &str) -> Result<Vec<(String, String)>, ParseError> {
     let mut result: Vec<(String, String)> = Vec::new();
-    for l in contents.lines() {
-        let letters = l.split_whitespace().collect::<Vec<&str>>();
-        result.push((letters[0].to_owned(), letters[1].to_owned()));
+    for l in lines(2, contents) {
+        let mut letters = l.text.split_whitespace();
+        let opponent = match letters.next() {
+            Some(s @ ("A" | "B" | "C")) => s,
+            other => return Err(l.error(other.unwrap_or(l.text), "`A`, `B` or `C`")),
+        };
+        let strategy = match letters.next() {
+            Some(s @ ("X" | "Y" | "Z")) => s,
+            other => return Err(l.error(other.unwrap_or(&l.text[l.text.len()..]), "`X`, `Y` or `Z`")),
+        };
+        result.push((opponent.to_owned(), strategy.to_owned()));
     }
 
-    return result;
+    return Ok(result);
 }
 
-pub fn solution() -> (String, String) {
-    // TODO: understand closures better to set global constant HashMaps and avoid duplicating `rules`
+pub fn calculate_points(code: &str) -> i32 {
+    let strategy_points: HashMap<&str, i32> = HashMap::from([("X", 1), ("Y", 2), ("Z", 3)]);
+    let match_points: HashMap<&str, i32> = HashMap::from([("win", 6), ("draw", 3)]);
     let rules: HashMap<&str, Vec<&str>> = HashMap::from([
         ("win", vec!["CX", "AY", "BZ"]),
         ("draw", vec!["AX", "BY", "CZ"]),
         ("loss", vec!["BX", "CY", "AZ"]),
     ]);
-    let second_rules: HashMap<&str, &str> =
-        HashMap::from([("X", "loss"), ("Y", "draw"), ("Z", "win")]);
-    let vector: Vec<(String, String)> = parser();
+    let mut result: i32 = 0;
+    result += strategy_points
+        .get(&code.chars().last().unwrap().to_string() as &str)
+        .unwrap();
+    if rules.get("win").unwrap().contains(&code) {
+        result += match_points.get("win").unwrap();
+    } else if rules.get("draw").unwrap().contains(&code) {
+        result += match_points.get("draw").unwrap();
+    }
+    return result;
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<(String, String)>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parser(input)
+    }
 
-    fn calculate_points(code: &str) -> i32 {
-        let strategy_points: HashMap<&str, i32> = HashMap::from([("X", 1), ("Y", 2), ("Z", 3)]);
-        let match_points: HashMap<&str, i32> = HashMap::from([("win", 6), ("draw", 3)]);
+    fn part1(input: &Self::Input) -> String {
+        let mut result1: i32 = 0;
+        for (s1, s2) in input {
+            let code = [s1.to_owned(), s2.to_owned()].join("");
+            result1 += calculate_points(&code);
+        }
+
+        return result1.to_string();
+    }
+
+    fn part2(input: &Self::Input) -> String {
+        // TODO: understand closures better to set global constant HashMaps and avoid duplicating `rules`
         let rules: HashMap<&str, Vec<&str>> = HashMap::from([
             ("win", vec!["CX", "AY", "BZ"]),
             ("draw", vec!["AX", "BY", "CZ"]),
             ("loss", vec!["BX", "CY", "AZ"]),
         ]);
-        let mut result: i32 = 0;
-        result += strategy_points
-            .get(&code.chars().last().unwrap().to_string() as &str)
-            .unwrap();
-        if rules.get("win").unwrap().contains(&code) {
-            result += match_points.get("win").unwrap();
-        } else if rules.get("draw").unwrap().contains(&code) {
-            result += match_points.get("draw").unwrap();
-        }
-        return result;
-    }
+        let second_rules: HashMap<&str, &str> =
+            HashMap::from([("X", "loss"), ("Y", "draw"), ("Z", "win")]);
 
-    let mut result1: i32 = 0;
-    for (s1, s2) in &vector {
-        let code = [s1.to_owned(), s2.to_owned()].join("");
-        result1 += calculate_points(&code);
-    }
+        let mut result2: i32 = 0;
+        for (s1, s2) in input {
+            let code = rules
+                .get(second_rules.get(s2 as &str).unwrap())
+                .unwrap()
+                .iter()
+                .find(|&&x| x.starts_with(s1))
+                .unwrap();
+            result2 += calculate_points(code);
+        }
 
-    let mut result2: i32 = 0;
-    for (s1, s2) in &vector {
-        let code = rules
-            .get(second_rules.get(&s2 as &str).unwrap())
-            .unwrap()
-            .iter()
-            .find(|&&x| x.starts_with(s1))
-            .unwrap();
-        result2 += calculate_points(&code);
+        return result2.to_string();
     }
-
-    return (result1.to_string(), result2.to_string());
 }
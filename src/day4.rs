@@ -1,32 +1,31 @@
-use crate::helpers::read_file;
+use crate::error::{lines, Line, ParseError};
+use crate::solution::Solution;
 use std::ops::RangeInclusive;
 
-type Pair = (RangeInclusive<u32>, RangeInclusive<u32>);
+pub type Pair = (RangeInclusive<u32>, RangeInclusive<u32>);
 
-fn parse_range(tuple: (&str, &str)) -> RangeInclusive<u32> {
+fn parse_range(line: &Line, tuple: (&str, &str)) -> Result<RangeInclusive<u32>, ParseError> {
     let (l, r) = tuple;
-    l.parse().unwrap()..=r.parse().unwrap()
+    Ok(line.parse(l, "a section number")?..=line.parse(r, "a section number")?)
 }
 
-fn parser() -> Vec<Pair> {
-    let contents = read_file("/inputs/day4.txt");
-    contents
-        .lines()
+pub fn parser(contents: &str) -> Result<Vec<Pair>, ParseError> {
+    lines(4, contents)
         .map(|l| {
-            let (r1, r2) = l.split_once(",").unwrap();
-            let (t1, t2) = (r1.split_once("-").unwrap(), r2.split_once("-").unwrap());
-            (parse_range(t1), parse_range(t2))
+            let (r1, r2) = l.split_once(l.text, ",")?;
+            let (t1, t2) = (l.split_once(r1, "-")?, l.split_once(r2, "-")?);
+            Ok((parse_range(&l, t1)?, parse_range(&l, t2)?))
         })
         .collect()
 }
 
-fn one_contains_another(ranges: &Pair) -> bool {
+pub fn one_contains_another(ranges: &Pair) -> bool {
     let (r1, r2) = ranges;
-    r1.contains(&r2.start()) && r1.contains(&r2.end())
-        || r2.contains(&r1.start()) && r2.contains(&r1.end())
+    r1.contains(r2.start()) && r1.contains(r2.end())
+        || r2.contains(r1.start()) && r2.contains(r1.end())
 }
 
-fn overlaps(ranges: &Pair) -> bool {
+pub fn overlaps(ranges: &Pair) -> bool {
     let (r1, r2) = ranges;
     r1.contains(r2.start())
         || r1.contains(r2.end())
@@ -34,20 +33,28 @@ fn overlaps(ranges: &Pair) -> bool {
         || r2.contains(r1.end())
 }
 
-pub fn solution() -> (String, String) {
-    let vector: Vec<Pair> = parser();
-    let result1: u32 = vector
-        .iter()
-        .filter(|pair| one_contains_another(pair))
-        .count()
-        .try_into()
-        .unwrap();
-    let result2: u32 = vector
-        .iter()
-        .filter(|pair| overlaps(pair))
-        .count()
-        .try_into()
-        .unwrap();
-
-    return (result1.to_string(), result2.to_string());
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Vec<Pair>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parser(input)
+    }
+
+    fn part1(input: &Self::Input) -> String {
+        let result1 = input
+            .iter()
+            .filter(|pair| one_contains_another(pair))
+            .count();
+
+        return result1.to_string();
+    }
+
+    fn part2(input: &Self::Input) -> String {
+        let result2 = input.iter().filter(|pair| overlaps(pair)).count();
+
+        return result2.to_string();
+    }
 }
+
@@ -1,13 +1,13 @@
-use crate::helpers::read_file;
-use regex::Regex;
+use crate::error::{lines, Line, ParseError};
+use crate::solution::Solution;
 
-type Operation = (usize, usize, usize);
+pub type Operation = (usize, usize, usize);
 
 fn parse_crate_line(l: &str, crates: &mut Vec<Vec<String>>) {
     let mut is_crate_item = false;
     l.chars().enumerate().for_each(|(i, c)| {
         if is_crate_item {
-            let crate_idx = (i + 3) / 4;
+            let crate_idx = i.div_ceil(4);
             while crates.len() < crate_idx + 1 {
                 crates.push(vec![]);
             }
@@ -17,46 +17,54 @@ fn parse_crate_line(l: &str, crates: &mut Vec<Vec<String>>) {
     });
 }
 
-fn get_operation(l: &str) -> Operation {
-    let re = Regex::new(r"\d+").unwrap();
-    let ops = re
-        .captures_iter(l)
-        .map(|cap| cap[0].parse::<usize>().unwrap())
-        .collect::<Vec<usize>>();
-    (ops[0], ops[1], ops[2])
+fn get_operation(l: &Line) -> Result<Operation, ParseError> {
+    let rest = l.strip_prefix("move ")?;
+    let (count, rest) = l.split_once(rest, " from ")?;
+    let (from, to) = l.split_once(rest, " to ")?;
+    Ok((
+        l.parse(count, "a number")?,
+        l.parse(from, "a stack number")?,
+        l.parse(to, "a stack number")?,
+    ))
 }
 
 fn find_start_of_operations(input: core::str::Lines<'_>) -> usize {
     let mut index: usize = 0;
     input.enumerate().for_each(|(i, l)| {
-        if l == "" {
+        if l.is_empty() {
             index = i;
         }
     });
     return index;
 }
 
-fn run_operation(op: Operation, crates: &mut Vec<Vec<String>>) {
+pub fn run_operation(op: Operation, crates: &mut [Vec<String>]) {
     for _ in 0..op.0 {
         let krate = crates[op.1].pop();
-        match krate {
-            Some(ok) => crates[op.2].push(ok),
-            None => (),
+        if let Some(ok) = krate {
+            crates[op.2].push(ok);
         }
     }
 }
 
-fn run_operation_updated(op: Operation, crates: &mut Vec<Vec<String>>) {
+pub fn run_operation_updated(op: Operation, crates: &mut [Vec<String>]) {
     let idx = crates[op.1].len() - op.0;
     let krates = crates[op.1][idx..].to_vec();
     crates[op.2] = [crates[op.2].clone(), krates].concat();
     crates[op.1] = crates[op.1][..idx].to_vec();
 }
 
-fn parser(contents: String) -> (Vec<Vec<String>>, Vec<Operation>) {
+pub fn parser(contents: String) -> Result<(Vec<Vec<String>>, Vec<Operation>), ParseError> {
     let mut crates: Vec<Vec<String>> = Vec::new();
     let mut operations: Vec<Operation> = Vec::new();
     let start_of_operations_index = find_start_of_operations(contents.lines());
+    if start_of_operations_index == 0 {
+        return Err(ParseError::end_of_input(
+            5,
+            &contents,
+            "a blank line between the crates and the rearrangement procedure",
+        ));
+    }
 
     // populate crates
     contents
@@ -68,43 +76,61 @@ fn parser(contents: String) -> (Vec<Vec<String>>, Vec<Operation>) {
         .for_each(|l| parse_crate_line(l, &mut crates));
 
     // populate operations
-    contents
-        .lines()
-        .skip(start_of_operations_index + 1)
-        .for_each(|l| {
-            operations.push(get_operation(l));
-        });
+    for l in lines(5, &contents).skip(start_of_operations_index + 1) {
+        let op = get_operation(&l)?;
+        for stack in [op.1, op.2] {
+            if stack == 0 || stack >= crates.len() {
+                let expected = format!("a stack between 1 and {}", crates.len().saturating_sub(1));
+                return Err(l.error(l.text, &expected));
+            }
+        }
+        operations.push(op);
+    }
 
-    return (crates, operations);
+    return Ok((crates, operations));
 }
 
-fn parse_answer(crates: &Vec<Vec<String>>) -> String {
+pub fn parse_answer(crates: &[Vec<String>]) -> String {
     crates
         .iter()
         .map(|c| c.last().unwrap_or(&"".to_owned()).to_owned())
         .collect()
 }
 
-pub fn solution() -> (String, String) {
-    let contents = read_file("/inputs/day5.txt");
-    let (crates, operations) = parser(contents);
-    let mut crates_one = crates.clone();
-    let mut crates_two = crates.clone();
-    
-    for op in operations {
-        run_operation(op, &mut crates_one);
-        run_operation_updated(op, &mut crates_two);
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = (Vec<Vec<String>>, Vec<Operation>);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parser(input.to_owned())
     }
 
-    let result1: String = parse_answer(&crates_one);
-    let result2: String = parse_answer(&crates_two);
+    fn part1(input: &Self::Input) -> String {
+        let (mut crates, operations) = input.clone();
+
+        for op in operations {
+            run_operation(op, &mut crates);
+        }
+
+        return parse_answer(&crates);
+    }
 
-    return (result1.to_string(), result2.to_string());
+    fn part2(input: &Self::Input) -> String {
+        let (mut crates, operations) = input.clone();
+
+        for op in operations {
+            run_operation_updated(op, &mut crates);
+        }
+
+        return parse_answer(&crates);
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::day5;
+    use crate::error::lines;
 
     const TEST_INPUT: &str = "
     [D]    
@@ -144,32 +170,52 @@ move 1 from 1 to 2";
 
     #[test]
     fn parse_operations() {
-        let operation_strings = &TEST_INPUT.lines().collect::<Vec<&str>>()[6..=7];
-        let op_one = day5::get_operation(operation_strings[0]);
-        let op_two = day5::get_operation(operation_strings[1]);
+        let operation_lines = &lines(5, TEST_INPUT).collect::<Vec<_>>()[6..=7];
+        let op_one = day5::get_operation(&operation_lines[0]).unwrap();
+        let op_two = day5::get_operation(&operation_lines[1]).unwrap();
         assert!(op_one == (1, 2, 1));
         assert!(op_two == (3, 1, 3));
     }
 
     #[test]
     fn result1() {
-        let (mut crates, operations) = day5::parser(TEST_INPUT.to_owned());
+        let (mut crates, operations) = day5::parser(TEST_INPUT.to_owned()).unwrap();
 
         for op in operations {
             day5::run_operation(op, &mut crates)
         };
 
-        assert!(day5::parse_answer(&crates) == "CMZ".to_owned());
+        assert!(day5::parse_answer(&crates) == "CMZ");
     }
 
     #[test]
     fn result2() {
-        let (mut crates, operations) = day5::parser(TEST_INPUT.to_owned());
+        let (mut crates, operations) = day5::parser(TEST_INPUT.to_owned()).unwrap();
 
         for op in operations {
             day5::run_operation_updated(op, &mut crates)
         };
 
-        assert!(day5::parse_answer(&crates) == "MCD".to_owned());
+        assert!(day5::parse_answer(&crates) == "MCD");
+    }
+
+    #[test]
+    fn parser_errors() {
+        let error = day5::parser(TEST_INPUT.replace("move 3 from 1", "move 3 from x")).unwrap_err();
+        assert_eq!((error.line, error.column), (8, 13));
+        assert_eq!((error.expected.as_str(), error.found.as_str()), ("a stack number", "`x`"));
+
+        let error = day5::parser(TEST_INPUT.replace("move 3", "move three")).unwrap_err();
+        assert_eq!((error.line, error.column, error.expected.as_str()), (8, 6, "a number"));
+
+        let error = day5::parser(TEST_INPUT.replace("from 1 to", "from 1 onto")).unwrap_err();
+        assert_eq!((error.line, error.expected.as_str()), (8, "` to `"));
+
+        let error = day5::parser(TEST_INPUT.replace("to 3", "to 4")).unwrap_err();
+        assert_eq!(error.line, 8);
+        assert_eq!(error.expected, "a stack between 1 and 3");
+
+        let error = day5::parser(TEST_INPUT.replace("\n\nmove", "\nmove")).unwrap_err();
+        assert_eq!(error.found, "end of input");
     }
 }
@@ -1,7 +1,8 @@
-use crate::helpers::read_file;
+use crate::error::ParseError;
+use crate::solution::Solution;
 use std::collections::HashSet;
 
-fn find_first_n_distinct(s: &str, n: usize) -> usize {
+pub fn find_first_n_distinct(s: &str, n: usize) -> usize {
     for i in n..s.chars().collect::<Vec<char>>().len() {
         let set: HashSet<char> = HashSet::from_iter(s.chars().skip(i - n).take(n));
         if set.len() == n {
@@ -11,12 +12,26 @@ fn find_first_n_distinct(s: &str, n: usize) -> usize {
     return 0;
 }
 
-pub fn solution() -> (String, String) {
-    let contents = read_file("/inputs/day6.txt");
-    let result1: usize = find_first_n_distinct(&contents, 4);
-    let result2: usize = find_first_n_distinct(&contents, 14);
+pub struct Day6;
 
-    return (result1.to_string(), result2.to_string());
+impl Solution for Day6 {
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(input.to_owned())
+    }
+
+    fn part1(input: &Self::Input) -> String {
+        let result1: usize = find_first_n_distinct(input, 4);
+
+        return result1.to_string();
+    }
+
+    fn part2(input: &Self::Input) -> String {
+        let result2: usize = find_first_n_distinct(input, 14);
+
+        return result2.to_string();
+    }
 }
 
 #[cfg(test)]
@@ -1,103 +1,825 @@
-use crate::helpers::read_file;
+use crate::error::{lines, Line, ParseError};
+use crate::output::json_string;
+use crate::solution::Solution;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
 
-#[allow(dead_code)]
-#[derive(Debug)]
-struct File {
-    name: String,
-    size: usize,
+/// Index of a directory in `FileSystem::dirs`
+pub type DirId = usize;
+
+/// The `/` directory
+pub const ROOT: DirId = 0;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct File {
+    pub name: String,
+    pub size: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Dir {
+    pub name: String,
+    /// `None` for the root only
+    pub parent: Option<DirId>,
+    /// Subdirectories in listing order
+    pub children: Vec<DirId>,
+    pub files: Vec<File>,
+    /// Total size of the files in this directory and below it, summed up by `build`
+    pub size: usize,
+    /// Subdirectories by name
+    child_ids: HashMap<String, DirId>,
+    /// Indices in `files` by name
+    file_ids: HashMap<String, usize>,
+}
+
+impl Dir {
+    fn new(name: String, parent: Option<DirId>) -> Dir {
+        Dir {
+            name,
+            parent,
+            children: Vec::new(),
+            files: Vec::new(),
+            size: 0,
+            child_ids: HashMap::new(),
+            file_ids: HashMap::new(),
+        }
+    }
 }
 
-#[derive(Debug)]
-struct FileSystem(HashMap<PathBuf, Vec<File>>);
+/// Directory tree of a terminal session. Directories are only ever added after their parent,
+/// so a parent always has a smaller id than its children.
+#[derive(Debug, Clone)]
+pub struct FileSystem {
+    dirs: Vec<Dir>,
+    warnings: Vec<ParseError>,
+}
+
+impl Default for FileSystem {
+    fn default() -> FileSystem {
+        FileSystem::new()
+    }
+}
 
 impl FileSystem {
-    /// Parses terminal commands and outputs into a HashMap
-    fn build(mut self, terminal_output: &str) -> self::FileSystem {
-        let cd_cmd = "$ cd";
-        let ls_cmd = "$ ls";
-        let dir_prefix = "dir ";
-
-        let mut curr_dir = PathBuf::from("/");
-        self.0.insert(curr_dir.clone(), vec![]);
-
-        for l in terminal_output.lines() {
-            if l.starts_with(cd_cmd) {
-                match l.replace(cd_cmd, "").trim() {
-                    "/" => curr_dir = PathBuf::from("/"),
-                    ".." => {
-                        curr_dir.pop();
-                    }
-                    dir_name => {
-                        curr_dir.push(dir_name);
+    /// File system with an empty root
+    pub fn new() -> FileSystem {
+        FileSystem {
+            dirs: vec![Dir::new("/".to_owned(), None)],
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Parses terminal commands and outputs into the directory tree. `cd` goes to `/`, `~` (the
+    /// root as well) or a path like `a/b/../c` made of directories listed before. Listing a
+    /// directory again updates its files instead of adding them twice. Unknown commands are
+    /// skipped with their output and reported by `warnings`.
+    pub fn build(mut self, terminal_output: &str) -> Result<self::FileSystem, ParseError> {
+        let mut curr_dir = ROOT;
+        // output of an unknown command, skipped until the next command
+        let mut skip_output = false;
+
+        for line in lines(7, terminal_output) {
+            let l = line.text.trim_end();
+            if let Some(command) = l.strip_prefix('$') {
+                let command = command.trim();
+                let (name, argument) = command.split_once(' ').unwrap_or((command, ""));
+                skip_output = false;
+                match name {
+                    "cd" => curr_dir = self.cd(&line, curr_dir, argument.trim())?,
+                    "ls" => (),
+                    _ => {
+                        self.warnings.push(line.error(name, "`cd` or `ls`"));
+                        skip_output = true;
                     }
                 }
-            } else if l.starts_with(ls_cmd) {
+            } else if skip_output || l.is_empty() {
                 continue;
-            } else if l.starts_with(dir_prefix) {
-                let dir_name = l.replace(dir_prefix, "").trim().to_owned();
-                let mut new_dir = curr_dir.clone();
-                new_dir.push(dir_name);
-                self.0.insert(new_dir, vec![]);
-            } else if l.len() > 0 {
-                let (size, name) = l.split_at(l.find(' ').unwrap());
-                self.0.get_mut(&curr_dir).unwrap().push(File {
-                    name: name.trim().to_string(),
-                    size: size.parse::<usize>().unwrap(),
-                });
+            } else if let Some(dir_name) = l.strip_prefix("dir ") {
+                let dir_name = dir_name.trim();
+                if self.child(curr_dir, dir_name).is_none() {
+                    self.add_dir(curr_dir, dir_name.to_owned());
+                }
+            } else {
+                let (size, name) = line.split_once(l, " ")?;
+                let size = line.parse::<usize>(size, "a file size")?;
+                let name = name.trim();
+                let dir = &mut self.dirs[curr_dir];
+                match dir.file_ids.get(name) {
+                    Some(idx) => dir.files[*idx].size = size,
+                    None => {
+                        dir.file_ids.insert(name.to_owned(), dir.files.len());
+                        dir.files.push(File {
+                            name: name.to_owned(),
+                            size,
+                        });
+                    }
+                }
             }
         }
-        self
+        self.sum_sizes();
+        Ok(self)
     }
 
-    fn sum_files_size(&self, dir: &PathBuf) -> usize {
-        match self.0.get(dir) {
-            Some(dir) => dir.iter().map(|f| f.size).sum::<usize>(),
-            None => 0,
+    /// Directory reached by `cd target` from `dir`, `target` being a slice of `line`. A missing
+    /// target goes home like in a shell.
+    fn cd(&self, line: &Line, dir: DirId, target: &str) -> Result<DirId, ParseError> {
+        let (mut dir, path) = match target.strip_prefix('~') {
+            Some(rest) if rest.is_empty() || rest.starts_with('/') => (ROOT, rest),
+            _ if target.is_empty() || target.starts_with('/') => (ROOT, target),
+            _ => (dir, target),
+        };
+        for segment in path.split('/') {
+            dir = match segment {
+                "" | "." => dir,
+                ".." => self.dirs[dir].parent.unwrap_or(ROOT),
+                name => self
+                    .child(dir, name)
+                    .ok_or_else(|| line.error(name, "a listed directory"))?,
+            };
         }
+        Ok(dir)
+    }
+
+    /// Fills in the size of every directory in one post-order pass. Children always have larger
+    /// ids than their parent, so going through the ids backwards reaches every directory after
+    /// all of its subdirectories, when its total is complete and can be added to its parent.
+    fn sum_sizes(&mut self) {
+        for dir in &mut self.dirs {
+            dir.size = dir.files.iter().map(|f| f.size).sum();
+        }
+        for id in (1..self.dirs.len()).rev() {
+            if let Some(parent) = self.dirs[id].parent {
+                self.dirs[parent].size += self.dirs[id].size;
+            }
+        }
+    }
+
+    fn add_dir(&mut self, parent: DirId, name: String) -> DirId {
+        let id = self.dirs.len();
+        self.dirs[parent].child_ids.insert(name.clone(), id);
+        self.dirs[parent].children.push(id);
+        self.dirs.push(Dir::new(name, Some(parent)));
+        id
+    }
+
+    /// Every directory, indexed by `DirId`
+    pub fn dirs(&self) -> &[Dir] {
+        &self.dirs
+    }
+
+    pub fn dir(&self, id: DirId) -> &Dir {
+        &self.dirs[id]
     }
 
-    fn dir_size(&self, dir: &PathBuf) -> usize {
-        let mut size: usize = 0;
-        self.0.keys().filter(|k| k.starts_with(dir)).for_each(|k| {
-            size += self.sum_files_size(k);
-        });
-        size
+    /// Subdirectory `name` of `dir`
+    pub fn child(&self, dir: DirId, name: &str) -> Option<DirId> {
+        self.dirs[dir].child_ids.get(name).copied()
     }
+
+    /// Directory at the absolute `path`
+    pub fn find(&self, path: &Path) -> Option<DirId> {
+        let mut dir = ROOT;
+        for component in path.components() {
+            match component {
+                Component::RootDir => dir = ROOT,
+                Component::ParentDir => dir = self.dirs[dir].parent.unwrap_or(ROOT),
+                Component::Normal(name) => dir = self.child(dir, name.to_str()?)?,
+                _ => (),
+            }
+        }
+        Some(dir)
+    }
+
+    /// Absolute path of `dir`
+    pub fn path(&self, dir: DirId) -> PathBuf {
+        let mut names = Vec::new();
+        let mut curr = Some(dir);
+        while let Some(id) = curr {
+            names.push(self.dirs[id].name.as_str());
+            curr = self.dirs[id].parent;
+        }
+        names.iter().rev().collect()
+    }
+
+    /// Number of directories between `dir` and the root, 0 for the root
+    pub fn depth(&self, dir: DirId) -> usize {
+        let mut depth = 0;
+        let mut curr = dir;
+        while let Some(parent) = self.dirs[curr].parent {
+            depth += 1;
+            curr = parent;
+        }
+        depth
+    }
+
+    /// Unknown commands met by `build`, each pointing at the command name
+    pub fn warnings(&self) -> &[ParseError] {
+        &self.warnings
+    }
+
+    /// Total size of `dir` and everything below it
+    pub fn dir_size(&self, dir: DirId) -> usize {
+        self.dirs[dir].size
+    }
+}
+
+/// Options of the `tree` and `du` reports
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReportOptions {
+    /// Largest entries first instead of the listing order
+    pub sort_by_size: bool,
+    /// Deepest level shown, the root being level 0
+    pub max_depth: Option<usize>,
 }
 
-pub fn solution() -> (String, String) {
-    let contents = read_file("/inputs/day7.txt");
+/// Size the way `du -h` prints it: bytes below 1K, then one decimal below 10 and whole numbers
+/// above, rounded up
+pub fn human_size(size: usize) -> String {
+    const UNITS: [&str; 6] = ["K", "M", "G", "T", "P", "E"];
+    if size < 1024 {
+        return size.to_string();
+    }
+    let mut value = size as f64;
+    let mut unit = 0;
+    value /= 1024.0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if value < 10.0 {
+        let tenths = (value * 10.0).ceil() / 10.0;
+        if tenths < 10.0 {
+            return format!("{tenths:.1}{}", UNITS[unit]);
+        }
+    }
+    format!("{}{}", value.ceil(), UNITS[unit])
+}
 
-    let fs = FileSystem(HashMap::new()).build(&contents);
-    let folders = fs.0.keys();
-    let result1: usize = folders.clone()
-        .map(|k| {
-            let size = fs.dir_size(k);
-            return if size < 100000 { size } else { 0 };
+impl FileSystem {
+    /// Directories and files of `dir` in report order, files after directories when sorted by
+    /// listing order
+    fn entries<'a>(&'a self, dir: DirId, options: &ReportOptions) -> Vec<Entry<'a>> {
+        let mut entries: Vec<Entry> = self.dirs[dir]
+            .children
+            .iter()
+            .map(|child| Entry::Dir(*child, self.dirs[*child].size))
+            .chain(self.dirs[dir].files.iter().map(Entry::File))
+            .collect();
+        if options.sort_by_size {
+            entries.sort_by_key(|entry| std::cmp::Reverse(entry.size()));
+        }
+        entries
+    }
+
+    /// Renders the tree like the `tree` command, with the total size of every directory:
+    ///
+    /// ```text
+    /// / (48381165)
+    /// ├── a (94853)
+    /// │   ├── e (584)
+    /// │   │   └── i (584)
+    /// ```
+    pub fn tree(&self, options: &ReportOptions) -> String {
+        let mut lines = vec![format!("/ ({})", self.dirs[ROOT].size)];
+        self.tree_lines(ROOT, "", 1, options, &mut lines);
+        lines.join("\n")
+    }
+
+    fn tree_lines(
+        &self,
+        dir: DirId,
+        prefix: &str,
+        depth: usize,
+        options: &ReportOptions,
+        lines: &mut Vec<String>,
+    ) {
+        if options.max_depth.is_some_and(|max| depth > max) {
+            return;
+        }
+        let entries = self.entries(dir, options);
+        for (idx, entry) in entries.iter().enumerate() {
+            let last = idx + 1 == entries.len();
+            let (branch, indent) =
+                if last { ("└── ", "    ") } else { ("├── ", "│   ") };
+            match entry {
+                Entry::Dir(child, size) => {
+                    lines.push(format!(
+                        "{prefix}{branch}{} ({size})",
+                        self.dirs[*child].name
+                    ));
+                    self.tree_lines(
+                        *child,
+                        &format!("{prefix}{indent}"),
+                        depth + 1,
+                        options,
+                        lines,
+                    );
+                }
+                Entry::File(file) => {
+                    lines.push(format!("{prefix}{branch}{} ({})", file.name, file.size))
+                }
+            }
+        }
+    }
+
+    /// Renders the directories like `du -h`: the size and path of every directory, each
+    /// directory after its subdirectories, or largest first when sorted by size
+    pub fn du(&self, options: &ReportOptions) -> String {
+        let mut dirs = Vec::new();
+        self.du_dirs(ROOT, 0, options, &mut dirs);
+        if options.sort_by_size {
+            dirs.sort_by_key(|dir| std::cmp::Reverse(self.dirs[*dir].size));
+        }
+        dirs.iter()
+            .map(|dir| {
+                format!(
+                    "{}\t{}",
+                    human_size(self.dirs[*dir].size),
+                    self.path(*dir).display()
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn du_dirs(&self, dir: DirId, depth: usize, options: &ReportOptions, dirs: &mut Vec<DirId>) {
+        if options.max_depth.is_some_and(|max| depth > max) {
+            return;
+        }
+        for entry in self.entries(
+            dir,
+            &ReportOptions {
+                sort_by_size: false,
+                ..*options
+            },
+        ) {
+            if let Entry::Dir(child, _) = entry {
+                self.du_dirs(child, depth + 1, options, dirs);
+            }
+        }
+        dirs.push(dir);
+    }
+}
+
+/// Line of a report
+enum Entry<'a> {
+    Dir(DirId, usize),
+    File(&'a File),
+}
+
+impl Entry<'_> {
+    fn size(&self) -> usize {
+        match self {
+            Entry::Dir(_, size) => *size,
+            Entry::File(file) => file.size,
+        }
+    }
+}
+
+/// Size of the disk and free space the update needs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiskSpace {
+    pub disk_size: usize,
+    pub update_size: usize,
+}
+
+impl DiskSpace {
+    /// Sizes of the puzzle
+    pub const PUZZLE: DiskSpace = DiskSpace {
+        disk_size: 70000000,
+        update_size: 30000000,
+    };
+
+    /// Space to free before the update fits on a disk with `used` taken
+    pub fn to_free(&self, used: usize) -> usize {
+        self.update_size
+            .saturating_sub(self.disk_size.saturating_sub(used))
+    }
+}
+
+impl Default for DiskSpace {
+    fn default() -> DiskSpace {
+        DiskSpace::PUZZLE
+    }
+}
+
+/// Directory or single file to delete
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Deletion {
+    Dir(DirId),
+    /// File at an index of the directory's `files`
+    File(DirId, usize),
+}
+
+/// Deletions freeing enough space for the update, made by `FileSystem::plan_cleanup`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CleanupPlan {
+    pub space: DiskSpace,
+    pub used: usize,
+    /// Directories and files to delete, none of them inside another, in listing order
+    pub deletions: Vec<Deletion>,
+    /// Total size of the deletions
+    pub freed: usize,
+    /// Smallest directory freeing enough on its own, what part 2 deletes
+    pub smallest_dir: DirId,
+    /// Bytes per unit of the search, 1 unless the sizes were too large to search byte by byte
+    pub unit: usize,
+}
+
+impl CleanupPlan {
+    /// Explains the plan, comparing it with deleting the smallest directory that frees enough:
+    ///
+    /// ```text
+    /// 48381165 used of 70000000, 21618835 free: the 30000000 update needs 8381165 more
+    /// delete file /c.dat (8504156)
+    /// frees 8504156, 122991 more than needed
+    /// the smallest directory freeing enough alone, /d, would free 24933642, 16429486 more
+    /// ```
+    pub fn explain(&self, fs: &FileSystem) -> String {
+        let to_free = self.space.to_free(self.used);
+        let mut lines = vec![format!(
+            "{} used of {}, {} free: the {} update needs {to_free} more",
+            self.used,
+            self.space.disk_size,
+            self.space.disk_size.saturating_sub(self.used),
+            self.space.update_size
+        )];
+        if self.deletions.is_empty() {
+            lines.push("nothing to delete".to_owned());
+            return lines.join("\n");
+        }
+        for deletion in &self.deletions {
+            let kind = if let Deletion::Dir(_) = deletion { "dir" } else { "file" };
+            lines.push(format!(
+                "delete {kind} {} ({})",
+                fs.deletion_path(*deletion).display(),
+                fs.deletion_size(*deletion)
+            ));
+        }
+        if self.unit > 1 {
+            lines.push(format!(
+                "sizes searched in units of {}, rounded down",
+                self.unit
+            ));
+        }
+        lines.push(format!(
+            "frees {}, {} more than needed",
+            self.freed,
+            self.freed - to_free
+        ));
+        let dir_size = fs.dir_size(self.smallest_dir);
+        let dir_path = fs.path(self.smallest_dir);
+        if dir_size > self.freed {
+            lines.push(format!(
+                "the smallest directory freeing enough alone, {}, would free {dir_size}, {} more",
+                dir_path.display(),
+                dir_size - self.freed
+            ));
+        } else {
+            lines.push(format!(
+                "as much as the smallest directory freeing enough alone, {}",
+                dir_path.display()
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+impl FileSystem {
+    pub fn deletion_size(&self, deletion: Deletion) -> usize {
+        match deletion {
+            Deletion::Dir(dir) => self.dirs[dir].size,
+            Deletion::File(dir, file) => self.dirs[dir].files[file].size,
+        }
+    }
+
+    pub fn deletion_path(&self, deletion: Deletion) -> PathBuf {
+        match deletion {
+            Deletion::Dir(dir) => self.path(dir),
+            Deletion::File(dir, file) => self.path(dir).join(&self.dirs[dir].files[file].name),
+        }
+    }
+
+    /// Smallest directory freeing enough space for the update on its own, `None` if even the
+    /// root is too small
+    pub fn smallest_dir_to_free(&self, space: &DiskSpace) -> Option<DirId> {
+        let to_free = space.to_free(self.dirs[ROOT].size);
+        (0..self.dirs.len())
+            .filter(|dir| self.dirs[*dir].size >= to_free)
+            .min_by_key(|dir| self.dirs[*dir].size)
+    }
+
+    /// Directories and files, none of them inside another, freeing enough space for the update
+    /// with the smallest total. `None` if deleting everything is not enough.
+    ///
+    /// Deleting a directory frees as much as deleting all of its files, so the smallest total
+    /// is a subset sum over the files. Directories whose files are all picked are then deleted
+    /// as a whole instead.
+    ///
+    /// The search keeps a bit per possible total, up to `MAX_SEARCH_BITS` in all. Past that, as
+    /// with the byte sizes of real disks, sizes are counted in larger units rounded down. The
+    /// plan still frees enough, but may free up to one unit per deleted file more than the best
+    /// one. If the rounding leaves no plan, the smallest directory freeing enough is deleted.
+    pub fn plan_cleanup(&self, space: &DiskSpace) -> Option<CleanupPlan> {
+        let used = self.dirs[ROOT].size;
+        let to_free = space.to_free(used);
+        let smallest_dir = self.smallest_dir_to_free(space)?;
+        // the smallest directory is a set of files freeing enough, no better set frees more
+        let limit = self.dirs[smallest_dir].size;
+        let files: Vec<(DirId, usize)> = (0..self.dirs.len())
+            .flat_map(|dir| (0..self.dirs[dir].files.len()).map(move |file| (dir, file)))
+            .filter(|(dir, file)| self.dirs[*dir].files[*file].size <= limit)
+            .collect();
+        let sizes: Vec<usize> = files
+            .iter()
+            .map(|(dir, file)| self.dirs[*dir].files[*file].size)
+            .collect();
+        // the checkpoints, a block and two working sets of `smallest_sum_reaching`
+        let bitsets = 2 * (sizes.len() as f64).sqrt().ceil() as usize + 2;
+        let unit = limit / (MAX_SEARCH_BITS / bitsets).max(64) + 1;
+        let units: Vec<usize> = sizes.iter().map(|size| size / unit).collect();
+        let Some(picked) = smallest_sum_reaching(&units, to_free.div_ceil(unit), limit / unit)
+        else {
+            return Some(CleanupPlan {
+                space: *space,
+                used,
+                deletions: vec![Deletion::Dir(smallest_dir)],
+                freed: limit,
+                smallest_dir,
+                unit,
+            });
+        };
+
+        let mut deleted: Vec<Vec<bool>> = self
+            .dirs
+            .iter()
+            .map(|dir| vec![false; dir.files.len()])
+            .collect();
+        for idx in &picked {
+            let (dir, file) = files[*idx];
+            deleted[dir][file] = true;
+        }
+        // children before parents, like `sum_sizes`
+        let mut whole = vec![false; self.dirs.len()];
+        for id in (0..self.dirs.len()).rev() {
+            let dir = &self.dirs[id];
+            whole[id] = dir.size > 0
+                && deleted[id].iter().all(|file| *file)
+                && dir
+                    .children
+                    .iter()
+                    .all(|child| whole[*child] || self.dirs[*child].size == 0);
+        }
+
+        let mut deletions = Vec::new();
+        let mut stack = vec![ROOT];
+        while let Some(dir) = stack.pop() {
+            if whole[dir] {
+                deletions.push(Deletion::Dir(dir));
+                continue;
+            }
+            deletions.extend(
+                (0..deleted[dir].len())
+                    .filter(|file| deleted[dir][*file])
+                    .map(|file| Deletion::File(dir, file)),
+            );
+            stack.extend(self.dirs[dir].children.iter().rev());
+        }
+
+        Some(CleanupPlan {
+            space: *space,
+            used,
+            deletions,
+            freed: picked.iter().map(|idx| sizes[*idx]).sum(),
+            smallest_dir,
+            unit,
         })
-        .sum();
+    }
+}
+
+/// Most bits `plan_cleanup` keeps at once to search for the smallest total, 64 MiB
+const MAX_SEARCH_BITS: usize = 1 << 29;
+
+/// Sums up to some limit reachable by adding some of the sizes seen so far, one bit per sum
+#[derive(Clone)]
+struct Sums(Vec<u64>);
 
-    let available_mem = 70000000;
-    let total_required_mem = 30000000;
-    let used_mem = fs.dir_size(&PathBuf::from("/"));
-    let delta_required_mem = total_required_mem - (available_mem - used_mem);
-    let dir_sizes = folders.map(|k| fs.dir_size(k));
-    let min_directory_size_greater_than_delta = dir_sizes
-        .filter(|s| *s > delta_required_mem)
-        .min()
-        .unwrap();
-    let result2 = min_directory_size_greater_than_delta;
+impl Sums {
+    fn new(limit: usize) -> Sums {
+        let mut bits = vec![0; limit / 64 + 1];
+        bits[0] = 1;
+        Sums(bits)
+    }
+
+    fn contains(&self, sum: usize) -> bool {
+        self.0
+            .get(sum / 64)
+            .is_some_and(|word| word >> (sum % 64) & 1 == 1)
+    }
+
+    fn add(&mut self, size: usize) {
+        let (words, bits) = (size / 64, size % 64);
+        for idx in (words..self.0.len()).rev() {
+            let mut shifted = self.0[idx - words] << bits;
+            if bits > 0 && idx > words {
+                shifted |= self.0[idx - words - 1] >> (64 - bits);
+            }
+            self.0[idx] |= shifted;
+        }
+    }
+}
 
-    return (result1.to_string(), result2.to_string());
+/// Indices of some of `sizes` adding up to the smallest sum of at least `target`, looking at
+/// sums up to `limit`. The reachable sums are only kept every √n sizes and recomputed block by
+/// block while walking back, which keeps the memory at √n bitsets of `limit` bits.
+fn smallest_sum_reaching(sizes: &[usize], target: usize, limit: usize) -> Option<Vec<usize>> {
+    let step = (sizes.len() as f64).sqrt().ceil().max(1.0) as usize;
+    // sums reachable before `sizes[block * step]`
+    let mut checkpoints = Vec::new();
+    let mut sums = Sums::new(limit);
+    for (idx, size) in sizes.iter().enumerate() {
+        if idx % step == 0 {
+            checkpoints.push(sums.clone());
+        }
+        sums.add(*size);
+    }
+    let mut sum = (target..=limit).find(|sum| sums.contains(*sum))?;
+
+    let mut picked = Vec::new();
+    for (block, checkpoint) in checkpoints.into_iter().enumerate().rev() {
+        let start = block * step;
+        let end = (start + step).min(sizes.len());
+        let mut before = vec![checkpoint];
+        for size in &sizes[start..end - 1] {
+            let mut next = before[before.len() - 1].clone();
+            next.add(*size);
+            before.push(next);
+        }
+        // `sum` is reachable with the sizes up to `idx`, it needs `idx` if not without it
+        for idx in (start..end).rev() {
+            if !before[idx - start].contains(sum) {
+                picked.push(idx);
+                sum -= sizes[idx];
+            }
+        }
+    }
+    picked.reverse();
+    Some(picked)
+}
+
+impl FileSystem {
+    /// JSON tree of the directories and files with their sizes, every directory being an object
+    /// `{"name": "a", "size": 94853, "dirs": [...], "files": [{"name": "f", "size": 29116}]}`
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        self.dir_json(ROOT, "", &mut json);
+        json
+    }
+
+    fn dir_json(&self, dir: DirId, indent: &str, json: &mut String) {
+        let inner = format!("{indent}  ");
+        let Dir {
+            name,
+            size,
+            children,
+            files,
+            ..
+        } = &self.dirs[dir];
+        let _ = write!(
+            json,
+            "{{\n{inner}\"name\": {},\n{inner}\"size\": {size},\n{inner}\"dirs\": [",
+            json_string(name)
+        );
+        for (idx, child) in children.iter().enumerate() {
+            let _ = write!(json, "{}\n{inner}  ", if idx == 0 { "" } else { "," });
+            self.dir_json(*child, &format!("{inner}  "), json);
+        }
+        if !children.is_empty() {
+            let _ = write!(json, "\n{inner}");
+        }
+        let _ = write!(json, "],\n{inner}\"files\": [");
+        for (idx, file) in files.iter().enumerate() {
+            let _ = write!(
+                json,
+                "{}\n{inner}  {{\"name\": {}, \"size\": {}}}",
+                if idx == 0 { "" } else { "," },
+                json_string(&file.name),
+                file.size
+            );
+        }
+        if !files.is_empty() {
+            let _ = write!(json, "\n{inner}");
+        }
+        let _ = write!(json, "]\n{indent}}}");
+    }
+
+    /// Creates the tree inside the `target` directory. Files are sparse: they have their size
+    /// but take no space on file systems that support it.
+    pub fn materialize(&self, target: &Path) -> io::Result<()> {
+        let mut paths = vec![target.to_path_buf()];
+        for (id, dir) in self.dirs.iter().enumerate() {
+            if let Some(parent) = dir.parent {
+                paths.push(paths[parent].join(entry_name(&dir.name)?));
+            }
+            fs::create_dir_all(&paths[id])?;
+            for file in &dir.files {
+                fs::File::create(paths[id].join(entry_name(&file.name)?))?
+                    .set_len(file.size as u64)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `name` if it is a single path component, so that materializing never writes outside the
+/// target directory
+fn entry_name(name: &str) -> io::Result<&str> {
+    let mut components = Path::new(name).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(_)), None) => Ok(name),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("`{name}` is not a file name"),
+        )),
+    }
+}
+
+/// Terminal session exploring the real directory `root` like the puzzle input: `$ ls` in
+/// every directory, then `$ cd` into each subdirectory, entries sorted by name. Symbolic
+/// links are left out.
+pub fn transcript(root: &Path) -> io::Result<String> {
+    let mut lines = vec!["$ cd /".to_owned()];
+    transcript_lines(root, &mut lines)?;
+    Ok(lines.join("\n"))
+}
+
+fn transcript_lines(dir: &Path, lines: &mut Vec<String>) -> io::Result<()> {
+    let mut entries = fs::read_dir(dir)?.collect::<io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+    lines.push("$ ls".to_owned());
+    let mut subdirs = Vec::new();
+    for entry in entries {
+        let name = entry.file_name().into_string().map_err(|name| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("{name:?} is not UTF-8"))
+        })?;
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            lines.push(format!("dir {name}"));
+            subdirs.push(name);
+        } else if file_type.is_file() {
+            lines.push(format!("{} {name}", entry.metadata()?.len()));
+        }
+    }
+    for name in subdirs {
+        lines.push(format!("$ cd {name}"));
+        transcript_lines(&dir.join(&name), lines)?;
+        lines.push("$ cd ..".to_owned());
+    }
+    Ok(())
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = FileSystem;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        FileSystem::new().build(input)
+    }
+
+    fn warnings(input: &Self::Input) -> Vec<ParseError> {
+        input.warnings().to_vec()
+    }
+
+    fn part1(input: &Self::Input) -> String {
+        let result1: usize = input
+            .dirs()
+            .iter()
+            .map(|dir| dir.size)
+            .filter(|size| *size < 100000)
+            .sum();
+
+        return result1.to_string();
+    }
+
+    fn part2(input: &Self::Input) -> String {
+        let smallest_dir = input
+            .smallest_dir_to_free(&DiskSpace::PUZZLE)
+            .expect("the disk is too small for the update");
+        let result2 = input.dir_size(smallest_dir);
+
+        return result2.to_string();
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::{collections::HashMap, path::PathBuf};
+    use std::path::{Path, PathBuf};
 
-    use crate::day7::FileSystem;
+    use crate::day7::{self, Deletion, DiskSpace, FileSystem, ReportOptions, ROOT};
 
     const TEST_INPUT: &str = "
 $ cd /
@@ -126,14 +848,364 @@ $ ls
 
     #[test]
     fn build() {
-        let mut fs: FileSystem = FileSystem(HashMap::new());
-        fs = fs.build(TEST_INPUT);
-        println!("FileSystem");
-        for (k, v) in &fs.0 {
-            println!("{}: {:?}", k.to_str().unwrap(), v);
-        }
-        let ae_dir = fs.0.get(&PathBuf::from("/a/e")).unwrap();
+        let mut fs: FileSystem = FileSystem::new();
+        fs = fs.build(TEST_INPUT).unwrap();
+        println!("{}", fs.tree(&ReportOptions::default()));
+        let ae = fs.find(Path::new("/a/e")).unwrap();
+        let ae_dir = &fs.dir(ae).files;
         assert_eq!(ae_dir[0].name, "i".to_string());
         assert_eq!(ae_dir[0].size, 584);
+
+        assert_eq!(fs.dirs().len(), 4);
+        assert_eq!(fs.path(ae), PathBuf::from("/a/e"));
+        assert_eq!(fs.depth(ae), 2);
+        assert_eq!(fs.dir(ae).parent, fs.find(Path::new("/a")));
+        assert_eq!(fs.find(Path::new("/a/e/..")), fs.find(Path::new("/a")));
+        assert_eq!(fs.find(Path::new("/x")), None);
+    }
+
+    #[test]
+    fn sizes() {
+        let fs = FileSystem::new().build(TEST_INPUT).unwrap();
+        let size = |path: &str| fs.dir_size(fs.find(Path::new(path)).unwrap());
+        assert_eq!(size("/a/e"), 584);
+        assert_eq!(size("/a"), 94853);
+        assert_eq!(size("/d"), 24933642);
+        assert_eq!(fs.dir_size(ROOT), 48381165);
+    }
+
+    #[test]
+    fn tree() {
+        let fs = FileSystem::new().build(TEST_INPUT).unwrap();
+        assert_eq!(
+            fs.tree(&ReportOptions::default()),
+            "\
+/ (48381165)
+├── a (94853)
+│   ├── e (584)
+│   │   └── i (584)
+│   ├── f (29116)
+│   ├── g (2557)
+│   └── h.lst (62596)
+├── d (24933642)
+│   ├── j (4060174)
+│   ├── d.log (8033020)
+│   ├── d.ext (5626152)
+│   └── k (7214296)
+├── b.txt (14848514)
+└── c.dat (8504156)"
+        );
+
+        let options = ReportOptions {
+            sort_by_size: true,
+            max_depth: Some(1),
+        };
+        assert_eq!(
+            fs.tree(&options),
+            "\
+/ (48381165)
+├── d (24933642)
+├── b.txt (14848514)
+├── c.dat (8504156)
+└── a (94853)"
+        );
+    }
+
+    #[test]
+    fn du() {
+        let fs = FileSystem::new().build(TEST_INPUT).unwrap();
+        assert_eq!(
+            fs.du(&ReportOptions::default()),
+            "584\t/a/e\n93K\t/a\n24M\t/d\n47M\t/"
+        );
+        let options = ReportOptions {
+            sort_by_size: true,
+            max_depth: Some(1),
+        };
+        assert_eq!(fs.du(&options), "47M\t/\n24M\t/d\n93K\t/a");
+    }
+
+    #[test]
+    fn human_size() {
+        assert_eq!(day7::human_size(0), "0");
+        assert_eq!(day7::human_size(1023), "1023");
+        assert_eq!(day7::human_size(1024), "1.0K");
+        assert_eq!(day7::human_size(1025), "1.1K");
+        assert_eq!(day7::human_size(10 * 1024 - 1), "10K");
+        assert_eq!(day7::human_size(5 * 1024 * 1024 * 1024), "5.0G");
+    }
+
+    #[test]
+    fn build_errors() {
+        let error = FileSystem::new()
+            .build("$ cd /\n$ ls\n12ab c.txt")
+            .unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(error.found, "`12ab`");
+
+        let error = FileSystem::new().build("$ cd /\n$ ls\n1234").unwrap_err();
+        assert_eq!(error.expected, "` `");
+
+        let error = FileSystem::new().build("$ cd x\n$ ls\n1 a").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 6));
+        assert_eq!(error.expected, "a listed directory");
+
+        let error = FileSystem::new()
+            .build(&format!("{TEST_INPUT}\n$ cd /a/e/../x"))
+            .unwrap_err();
+        assert_eq!((error.line, error.column), (25, 14));
+        assert_eq!(error.found, "`x`");
+    }
+
+    #[test]
+    fn build_grammar() {
+        let fs = FileSystem::new().build(TEST_INPUT).unwrap();
+        // listing everything again, reaching the directories through other paths
+        let session = format!(
+            "{TEST_INPUT}
+$ cd ~
+$ ls
+dir a
+14848514 b.txt
+$ cd a/e/../e
+$ ls
+584 i
+$ tar czf backup.tgz .
+backup.tgz
+$ cd ~/d
+$ ls
+4060174 j
+$ cd ../a/./
+$ ls
+62596 h.lst
+$ cd
+$ ls"
+        );
+        let relisted = FileSystem::new().build(&session).unwrap();
+        assert_eq!(
+            relisted.tree(&ReportOptions::default()),
+            fs.tree(&ReportOptions::default())
+        );
+        assert_eq!(relisted.dirs().len(), 4);
+
+        let warnings = relisted.warnings();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!((warnings[0].line, warnings[0].column), (32, 3));
+        assert_eq!(warnings[0].found, "`tar`");
+        assert!(fs.warnings().is_empty());
+
+        let fs = FileSystem::new().build("$ ls\n10 a\n$ ls\n12 a").unwrap();
+        assert_eq!(fs.dir(ROOT).files.len(), 1);
+        assert_eq!(fs.dir_size(ROOT), 12);
+    }
+
+    #[test]
+    fn disk_space() {
+        let fs = FileSystem::new().build(TEST_INPUT).unwrap();
+        assert_eq!(DiskSpace::PUZZLE.to_free(48381165), 8381165);
+        assert_eq!(DiskSpace::PUZZLE.to_free(100), 0);
+        let d = fs.find(Path::new("/d"));
+        assert_eq!(fs.smallest_dir_to_free(&DiskSpace::PUZZLE), d);
+
+        let space = DiskSpace {
+            disk_size: 48381165,
+            update_size: 90000,
+        };
+        assert_eq!(fs.smallest_dir_to_free(&space), fs.find(Path::new("/a")));
+        let space = DiskSpace {
+            disk_size: 50000000,
+            update_size: 60000000,
+        };
+        assert_eq!(fs.smallest_dir_to_free(&space), None);
+        assert_eq!(fs.plan_cleanup(&space), None);
+    }
+
+    #[test]
+    fn plan_cleanup() {
+        let fs = FileSystem::new().build(TEST_INPUT).unwrap();
+        let plan = fs.plan_cleanup(&DiskSpace::PUZZLE).unwrap();
+        let c = fs
+            .dir(ROOT)
+            .files
+            .iter()
+            .position(|file| file.name == "c.dat");
+        assert_eq!(plan.deletions, vec![Deletion::File(ROOT, c.unwrap())]);
+        assert_eq!(plan.freed, 8504156);
+        assert_eq!(
+            plan.explain(&fs),
+            "\
+48381165 used of 70000000, 21618835 free: the 30000000 update needs 8381165 more
+delete file /c.dat (8504156)
+frees 8504156, 122991 more than needed
+the smallest directory freeing enough alone, /d, would free 24933642, 16429486 more"
+        );
+
+        // every file of /a is needed, so /a goes as a whole
+        let space = DiskSpace {
+            disk_size: 48381165,
+            update_size: 94853,
+        };
+        let plan = fs.plan_cleanup(&space).unwrap();
+        assert_eq!(
+            plan.deletions,
+            vec![Deletion::Dir(fs.find(Path::new("/a")).unwrap())]
+        );
+        assert!(plan
+            .explain(&fs)
+            .ends_with("as much as the smallest directory freeing enough alone, /a"));
+
+        let space = DiskSpace {
+            disk_size: 100000000,
+            update_size: 100,
+        };
+        let plan = fs.plan_cleanup(&space).unwrap();
+        assert_eq!((plan.deletions.len(), plan.freed), (0, 0));
+        assert!(plan.explain(&fs).ends_with("nothing to delete"));
+    }
+
+    #[test]
+    fn plan_cleanup_large_disk() {
+        // the example in bytes of a disk a million times larger
+        let input: Vec<String> = TEST_INPUT
+            .lines()
+            .map(|line| match line.split_once(' ') {
+                Some((size, name)) if size.parse::<usize>().is_ok() => {
+                    format!("{} {name}", size.parse::<usize>().unwrap() * 1_000_000)
+                }
+                _ => line.to_owned(),
+            })
+            .collect();
+        let fs = FileSystem::new().build(&input.join("\n")).unwrap();
+        let space = DiskSpace {
+            disk_size: 70_000_000_000_000,
+            update_size: 30_000_000_000_000,
+        };
+        let plan = fs.plan_cleanup(&space).unwrap();
+        assert!(plan.unit > 1);
+        assert!(plan.freed >= space.to_free(fs.dir_size(ROOT)));
+        assert!(plan.freed <= 8504156 * 1_000_000 + plan.deletions.len() * plan.unit);
+        assert!(plan
+            .explain(&fs)
+            .contains(&format!("sizes searched in units of {}", plan.unit)));
+    }
+
+    #[test]
+    fn plan_cleanup_is_optimal() {
+        // the example with sizes in thousands, small enough to try every set of files
+        let input: Vec<String> = TEST_INPUT
+            .lines()
+            .map(|line| match line.split_once(' ') {
+                Some((size, name)) if size.parse::<usize>().is_ok() => {
+                    format!("{} {name}", size.parse::<usize>().unwrap() / 1000)
+                }
+                _ => line.to_owned(),
+            })
+            .collect();
+        let fs = FileSystem::new().build(&input.join("\n")).unwrap();
+        let sizes: Vec<usize> = fs
+            .dirs()
+            .iter()
+            .flat_map(|dir| dir.files.iter().map(|file| file.size))
+            .collect();
+        for update_size in (0..=fs.dir_size(ROOT)).step_by(97) {
+            let space = DiskSpace {
+                disk_size: fs.dir_size(ROOT),
+                update_size,
+            };
+            let to_free = space.to_free(fs.dir_size(ROOT));
+            let best = (0..1usize << sizes.len())
+                .map(|set| {
+                    (0..sizes.len())
+                        .filter(|idx| set >> idx & 1 == 1)
+                        .map(|idx| sizes[idx])
+                        .sum::<usize>()
+                })
+                .filter(|sum| *sum >= to_free)
+                .min();
+            let plan = fs.plan_cleanup(&space).unwrap();
+            assert_eq!(plan.unit, 1);
+            assert_eq!(Some(plan.freed), best);
+            let total: usize = plan.deletions.iter().map(|d| fs.deletion_size(*d)).sum();
+            assert_eq!(total, plan.freed);
+        }
+    }
+
+    /// Empty directory for a test, in the system temporary directory
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc22-day7-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn to_json() {
+        let fs = FileSystem::new()
+            .build("$ cd /\n$ ls\ndir a\n5 x\"y\n$ cd a\n$ ls\ndir e\n3 b")
+            .unwrap();
+        assert_eq!(
+            fs.to_json(),
+            r#"{
+  "name": "/",
+  "size": 8,
+  "dirs": [
+    {
+      "name": "a",
+      "size": 3,
+      "dirs": [
+        {
+          "name": "e",
+          "size": 0,
+          "dirs": [],
+          "files": []
+        }
+      ],
+      "files": [
+        {"name": "b", "size": 3}
+      ]
+    }
+  ],
+  "files": [
+    {"name": "x\"y", "size": 5}
+  ]
+}"#
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let fs = FileSystem::new().build(TEST_INPUT).unwrap();
+        let dir = scratch_dir("round-trip");
+        fs.materialize(&dir).unwrap();
+        assert_eq!(
+            std::fs::metadata(dir.join("d/d.log")).unwrap().len(),
+            8033020
+        );
+
+        let session = day7::transcript(&dir).unwrap();
+        assert!(session.starts_with("$ cd /\n$ ls\ndir a\n14848514 b.txt\n"));
+        let copy = FileSystem::new().build(&session).unwrap();
+        let options = ReportOptions {
+            sort_by_size: true,
+            max_depth: None,
+        };
+        assert_eq!(copy.tree(&options), fs.tree(&options));
+        assert_eq!(copy.to_json().len(), fs.to_json().len());
+
+        let copy_dir = scratch_dir("round-trip-copy");
+        copy.materialize(&copy_dir).unwrap();
+        assert_eq!(day7::transcript(&copy_dir).unwrap(), session);
+        std::fs::remove_dir_all(dir).unwrap();
+        std::fs::remove_dir_all(copy_dir).unwrap();
+    }
+
+    #[test]
+    fn materialize_errors() {
+        let dir = scratch_dir("escape");
+        let fs = FileSystem::new().build("$ ls\n1 ../x").unwrap();
+        let error = fs.materialize(&dir).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+        assert!(!dir.join("../x").exists());
+        std::fs::remove_dir_all(dir).unwrap();
+        assert!(day7::transcript(Path::new("/no/such/dir")).is_err());
     }
 }
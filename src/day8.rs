@@ -1,11 +1,15 @@
-use crate::helpers::read_file;
+use crate::error::ParseError;
+use crate::helpers::{ansi, Direction8, Grid};
+use crate::solution::Solution;
 
-type Forest = Vec<Vec<TreeInForest>>;
+/// Trees indexed `(row, col)`
+pub type Forest = Grid<TreeInForest>;
 
 #[derive(Debug, Clone)]
-struct TreeInForest {
-    height: u8,
-    visible: bool,
+pub struct TreeInForest {
+    pub height: u8,
+    /// Visible from outside the forest under the line of sight of `mark_visible`
+    pub visible: bool,
 }
 
 impl TreeInForest {
@@ -17,121 +21,291 @@ impl TreeInForest {
     }
 }
 
-fn parser<'a>(input: &'a str) -> Forest {
-    let line_count = input.lines().count();
-    let col_count = input.lines().nth(1).unwrap().chars().count();
-    let mut forest: Forest = vec![
-        vec![
-            TreeInForest {
-                height: 0,
-                visible: false
-            };
-            line_count
-        ];
-        col_count
-    ];
-
-    let lines: Vec<&str> = input.lines().collect();
-    let columns = |l: &'a str| l.chars().collect::<Vec<char>>();
-
-    // 1st iteration: build the forest and find visibility from the left and top
-    let mut max_top: Vec<u8> = vec![0; col_count];
-    let mut max_left: u8 = 0;
-    for (line, line_str) in lines.iter().enumerate() {
-        for (col, char) in columns(line_str).iter().enumerate() {
-            let mut tree = TreeInForest::new(char.to_digit(10).unwrap() as u8);
-            tree.visible =
-                col == 0 || line == 0 || tree.height > max_left || tree.height > max_top[col];
-            max_left = if col == 0 { tree.height } else { max_left.max(tree.height) };
-            max_top[col] = if line == 0 { tree.height } else { max_top[col].max(tree.height) };
-            forest[col][line] = tree;
+/// Trees that block the view of a tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Blocking {
+    /// Trees as tall as it or taller, the puzzle rule
+    TallerOrEqual,
+    /// Only trees strictly taller than it
+    Taller,
+}
+
+impl Blocking {
+    /// Whether a tree of height `other` blocks the view of a tree of height `height`
+    pub fn blocks(self, other: u8, height: u8) -> bool {
+        match self {
+            Blocking::TallerOrEqual => other >= height,
+            Blocking::Taller => other > height,
         }
     }
+}
 
-    // 2nd iteration: check visibility from the right and bottom
-    let mut max_bottom: Vec<u8> = vec![0; line_count];
-    let mut max_right = 0;
-    for (line, line_str) in lines.iter().enumerate().rev() {
-        for (col, _) in columns(line_str).iter().enumerate().rev() {
-            let tree = &mut forest[col][line];
-            tree.visible = col == col_count - 1
-                || line == line_count - 1
-                || tree.visible
-                || tree.height > max_right
-                || tree.height > max_bottom[col];
-            max_right = if col == col_count - 1 { tree.height } else { max_right.max(tree.height) };
-            max_bottom[col] = if line == line_count - 1 {
-                tree.height
-            } else {
-                max_bottom[col].max(tree.height)
-            };
-        }
+/// Directions a tree is looked at from, or looks to, and the trees blocking the view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineOfSight<'a> {
+    pub directions: &'a [Direction8],
+    pub blocking: Blocking,
+}
+
+impl LineOfSight<'static> {
+    /// Up, right, down and left, blocked by trees as tall or taller
+    pub const PUZZLE: LineOfSight<'static> = LineOfSight {
+        directions: &Direction8::ORTHOGONAL,
+        blocking: Blocking::TallerOrEqual,
+    };
+
+    /// Diagonals too, blocked by trees as tall or taller
+    pub const ALL_DIRECTIONS: LineOfSight<'static> = LineOfSight {
+        directions: &Direction8::ALL,
+        blocking: Blocking::TallerOrEqual,
+    };
+}
+
+impl Default for LineOfSight<'static> {
+    fn default() -> LineOfSight<'static> {
+        LineOfSight::PUZZLE
     }
+}
 
-    forest
+/// `(row, col)` offset of a step in `direction`
+fn step(direction: Direction8) -> (isize, isize) {
+    let delta = direction.delta();
+    (delta.y as isize, delta.x as isize)
 }
 
-fn count_visible_trees(forest: &Forest) -> usize {
+/// Lines of trees parallel to `direction`, each starting on the edge `direction` points to and
+/// going back, so the trees before a tree in its line are the ones it sees looking that way
+fn sight_lines(
+    forest: &Forest,
+    direction: Direction8,
+) -> impl Iterator<Item = impl Iterator<Item = (usize, usize)> + '_> + '_ {
+    let (d_row, d_col) = step(direction);
     forest
+        .positions()
+        .filter(move |pos| forest.offset(*pos, (d_row, d_col)).is_none())
+        .map(move |pos| std::iter::once(pos).chain(forest.ray(pos, (-d_row, -d_col))))
+}
+
+/// Which trees are visible from outside the forest, looking at them from any of the
+/// directions of `sight`
+pub fn visibility(forest: &Forest, sight: &LineOfSight) -> Grid<bool> {
+    let mut visible = Grid::new(forest.height(), forest.width(), false);
+    for direction in sight.directions {
+        for line in sight_lines(forest, *direction) {
+            let mut tallest: Option<u8> = None;
+            for pos in line {
+                let height = forest[pos].height;
+                if tallest.is_none_or(|tallest| !sight.blocking.blocks(tallest, height)) {
+                    visible[pos] = true;
+                }
+                tallest = tallest.max(Some(height));
+            }
+        }
+    }
+    visible
+}
+
+/// Sets the `visible` flag of every tree under `sight`
+pub fn mark_visible(forest: &mut Forest, sight: &LineOfSight) {
+    let visible = visibility(forest, sight);
+    for (tree, visible) in forest.iter_mut().zip(visible.iter()) {
+        tree.visible = *visible;
+    }
+}
+
+/// Forest of any number of rows and columns, an empty input being an empty forest. Trees are
+/// marked visible under the puzzle's line of sight.
+pub fn parser(input: &str) -> Result<Forest, ParseError> {
+    let mut forest: Forest = Grid::parse(8, input, "trees", |line, height| {
+        Ok(TreeInForest::new(line.parse(height, "a tree height")?))
+    })?;
+    mark_visible(&mut forest, &LineOfSight::PUZZLE);
+
+    Ok(forest)
+}
+
+pub fn count_visible_trees(forest: &Forest) -> usize {
+    forest.iter().filter(|tree| tree.visible).count()
+}
+
+/// Product of the viewing distances from the tree at `(row, col)` in the directions of
+/// `sight`. Walks out from the tree, `scenic_scores` is the fast way to score the whole forest.
+pub fn scenic_score(forest: &Forest, pos: (usize, usize), sight: &LineOfSight) -> usize {
+    let height = forest[pos].height;
+    sight
+        .directions
         .iter()
-        .map(|col| {
-            col.iter()
-                .map(|tree| if tree.visible { 1 } else { 0 })
-                .sum::<usize>()
+        .map(|direction| {
+            let mut distance = 0;
+            for other in forest.ray(pos, step(*direction)) {
+                distance += 1;
+                if sight.blocking.blocks(forest[other].height, height) {
+                    break;
+                }
+            }
+            distance
         })
-        .sum()
+        .product()
+}
+
+/// Scenic score of every tree, the same as `scenic_score` but in one pass over each line of
+/// trees per direction, so O(rows × cols × directions) for the whole forest. Along a line,
+/// the stack keeps the trees not yet hidden by a tree blocking them, so after popping the ones
+/// that don't block the current tree its top is the tree it sees last.
+pub fn scenic_scores(forest: &Forest, sight: &LineOfSight) -> Grid<usize> {
+    let mut scores = Grid::new(forest.height(), forest.width(), 1);
+    for direction in sight.directions {
+        for line in sight_lines(forest, *direction) {
+            let mut stack: Vec<(usize, u8)> = Vec::new();
+            for (idx, pos) in line.enumerate() {
+                let height = forest[pos].height;
+                while stack
+                    .last()
+                    .is_some_and(|(_, other)| !sight.blocking.blocks(*other, height))
+                {
+                    stack.pop();
+                }
+                scores[pos] *= stack.last().map_or(idx, |(blocking, _)| idx - blocking);
+                stack.push((idx, height));
+            }
+        }
+    }
+    scores
+}
+
+/// Positions of the `k` highest scenic scores, best first, ties in row order
+pub fn top_scenic(scores: &Grid<usize>, k: usize) -> Vec<(usize, usize)> {
+    let mut positions: Vec<(usize, usize)> = scores.positions().collect();
+    positions.sort_by_key(|pos| std::cmp::Reverse(scores[*pos]));
+    positions.truncate(k);
+    positions
+}
+
+/// What `render_heatmap` and the image exports show of every tree
+#[derive(Debug, Clone, Copy)]
+pub enum Heatmap<'a> {
+    /// No colours, the heights of visible trees and `.` for hidden ones. Images show heights.
+    Plain,
+    /// Visible trees light and hidden ones dark, taller trees a bit lighter in both
+    Visibility,
+    /// Lighter trees have higher scenic scores, from `scenic_scores`
+    ScenicScores(&'a Grid<usize>),
 }
 
-fn scenic_score(forest: &Forest, x: usize, y: usize) -> usize {
-    let max_col = forest.len();
-    let max_line = forest[0].len();
-    let taller_or_equal = |t: &TreeInForest| t.height >= forest[x][y].height;
-    if x == max_col - 1 || y == max_line - 1 || x == 0 || y == 0 {
-        return 0;
+impl Heatmap<'_> {
+    /// Gray level of every tree, from 0.0 for black to 1.0 for white
+    fn levels(&self, forest: &Forest) -> Grid<f64> {
+        match self {
+            Heatmap::Plain => forest.map(|tree| tree.height as f64 / 9.0),
+            Heatmap::Visibility => forest.map(|tree| {
+                let height = tree.height as f64 / 9.0 * 0.4;
+                if tree.visible {
+                    0.6 + height
+                } else {
+                    height
+                }
+            }),
+            Heatmap::ScenicScores(scores) => {
+                let max = scores.iter().max().copied().unwrap_or(0).max(1);
+                scores.map(|score| *score as f64 / max as f64)
+            }
+        }
     }
-    let left = 1 + forest[..x]
-        .iter()
-        .rev()
-        .position(|c| taller_or_equal(&c[y]))
-        .unwrap_or(x - 1);
-    let right = 1 + forest[x+1..]
-        .iter()
-        .position(|c| taller_or_equal(&c[y]))
-        .unwrap_or(max_col - 2 - x);
-    let up = 1 + forest[x][..y]
-        .iter()
-        .rev()
-        .position(taller_or_equal)
-        .unwrap_or(y - 1);
-    let down = 1 + forest[x][y+1..]
-        .iter()
-        .position(taller_or_equal)
-        .unwrap_or(max_line - 2 - y);
+}
 
-    left * right * up * down
+/// Positions of `highlight` as a mask of the forest
+fn highlight_mask(forest: &Forest, highlight: &[(usize, usize)]) -> Grid<bool> {
+    let mut mask = Grid::new(forest.height(), forest.width(), false);
+    for pos in highlight {
+        mask[*pos] = true;
+    }
+    mask
+}
+
+/// Draws the heights of the trees over a gray background following `heatmap`. The trees of
+/// `highlight`, like the `top_scenic` ones, stand out in yellow, or as `*` when plain.
+pub fn render_heatmap(forest: &Forest, heatmap: &Heatmap, highlight: &[(usize, usize)]) -> String {
+    let levels = heatmap.levels(forest);
+    let highlighted = highlight_mask(forest, highlight);
+    let cell = |pos: (usize, usize)| -> String {
+        let height = forest[pos].height.to_string();
+        match heatmap {
+            Heatmap::Plain if highlighted[pos] => "*".to_owned(),
+            Heatmap::Plain if !forest[pos].visible => ".".to_owned(),
+            Heatmap::Plain => height,
+            _ if highlighted[pos] => ansi::highlight(&height),
+            _ => ansi::shade(&height, levels[pos]),
+        }
+    };
+    (0..forest.height())
+        .map(|row| (0..forest.width()).map(|col| cell((row, col))).collect())
+        .collect::<Vec<String>>()
+        .join("\n")
 }
 
-pub fn solution() -> (String, String) {
-    let contents = read_file("/inputs/day8.txt");
+/// Colour of the highlighted trees in images, the yellow of the terminal
+const HIGHLIGHT_RGB: [u8; 3] = [255, 200, 0];
 
-    let forest = parser(&contents);
+fn gray(level: f64) -> u8 {
+    (level.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// Binary PGM image of `heatmap`, one gray pixel per tree
+pub fn to_pgm(forest: &Forest, heatmap: &Heatmap) -> Vec<u8> {
+    let mut image = format!("P5\n{} {}\n255\n", forest.width(), forest.height()).into_bytes();
+    image.extend(heatmap.levels(forest).iter().map(|level| gray(*level)));
+    image
+}
 
-    let result1: usize = count_visible_trees(&forest);
-    
-    let mut result2: usize = 0;
-    for (x, col) in forest.iter().enumerate() {
-        for (y, _) in col.iter().enumerate() {
-            result2 = result2.max(scenic_score(&forest, x, y));
+/// Binary PPM image of `heatmap`, one pixel per tree, gray except for the yellow trees of
+/// `highlight`
+pub fn to_ppm(forest: &Forest, heatmap: &Heatmap, highlight: &[(usize, usize)]) -> Vec<u8> {
+    let mut image = format!("P6\n{} {}\n255\n", forest.width(), forest.height()).into_bytes();
+    let highlighted = highlight_mask(forest, highlight);
+    for (level, highlighted) in heatmap.levels(forest).iter().zip(highlighted.iter()) {
+        if *highlighted {
+            image.extend(HIGHLIGHT_RGB);
+        } else {
+            image.extend([gray(*level); 3]);
         }
     }
-    
+    image
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Forest;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parser(input)
+    }
+
+    fn part1(input: &Self::Input) -> String {
+        let result1: usize = count_visible_trees(input);
+
+        return result1.to_string();
+    }
+
+    fn part2(input: &Self::Input) -> String {
+        let result2: usize = scenic_scores(input, &LineOfSight::PUZZLE)
+            .iter()
+            .copied()
+            .max()
+            .unwrap_or(0);
 
-    return (result1.to_string(), result2.to_string());
+        return result2.to_string();
+    }
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
-    use crate::day8;
+    use crate::day8::{self, Blocking, Day8, Forest, Heatmap, LineOfSight};
+    use crate::helpers::{ansi, Direction8};
+    use crate::solution::Solution;
+
+    const PUZZLE: LineOfSight = LineOfSight::PUZZLE;
 
     const TEST_INPUT: &str = "\
 30373
@@ -142,74 +316,283 @@ mod tests {
 
     #[test]
     fn parser() {
-        let forest = day8::parser(&TEST_INPUT);
-        let mut printable_transpose = vec![vec![]; forest[0].len()];
-        forest.iter().for_each(|col| {
-            for (line, item) in col.iter().enumerate() {
-                printable_transpose[line].push(item)
-            }
-        });
-        for line in printable_transpose {
-            println!(
-                "{:?}",
-                line.iter()
-                    .map(|x| {
-                        if x.visible {
-                            format!("{} ", x.height)
-                        } else {
-                            format!("{}*", x.height)
-                        }
-                    })
-                    .collect::<Vec<String>>()
-            );
-        }
+        let forest = day8::parser(TEST_INPUT).unwrap();
+        assert_eq!(
+            day8::render_heatmap(&forest, &Heatmap::Plain, &[]),
+            "30373\n255.2\n65.32\n3.5.9\n35390"
+        );
 
         // assert positioning
-        assert_eq!(forest[0][0].height, 3);
-        assert_eq!(forest[0][1].height, 2);
-        assert_eq!(forest[0][2].height, 6);
-        assert_eq!(forest[0][3].height, 3);
-        assert_eq!(forest[0][4].height, 3);
-        assert_eq!(forest[1][0].height, 0);
-        assert_eq!(forest[1][1].height, 5);
-        assert_eq!(forest[2][2].height, 3);
-        assert_eq!(forest[3][3].height, 4);
-        assert_eq!(forest[3][4].height, 9);
-        assert_eq!(forest[4][4].height, 0);
+        assert_eq!(forest[(0, 0)].height, 3);
+        assert_eq!(forest[(1, 0)].height, 2);
+        assert_eq!(forest[(2, 0)].height, 6);
+        assert_eq!(forest[(3, 0)].height, 3);
+        assert_eq!(forest[(4, 0)].height, 3);
+        assert_eq!(forest[(0, 1)].height, 0);
+        assert_eq!(forest[(1, 1)].height, 5);
+        assert_eq!(forest[(2, 2)].height, 3);
+        assert_eq!(forest[(3, 3)].height, 4);
+        assert_eq!(forest[(4, 3)].height, 9);
+        assert_eq!(forest[(4, 4)].height, 0);
 
         // assert visibility
-        assert_eq!(forest[0][0].visible, true);
-        assert_eq!(forest[0][1].visible, true);
-        assert_eq!(forest[0][2].visible, true);
-        assert_eq!(forest[0][3].visible, true);
-        assert_eq!(forest[0][4].visible, true);
-        assert_eq!(forest[1][0].visible, true);
-        assert_eq!(forest[1][1].visible, true);
-        assert_eq!(forest[1][2].visible, true);
-        assert_eq!(forest[1][3].visible, false);
-        assert_eq!(forest[2][2].visible, false);
-        assert_eq!(forest[3][2].visible, true);
-        assert_eq!(forest[3][3].visible, false);
-        assert_eq!(forest[3][4].visible, true);
-        assert_eq!(forest[4][4].visible, true);
+        assert_eq!(forest[(0, 0)].visible, true);
+        assert_eq!(forest[(1, 0)].visible, true);
+        assert_eq!(forest[(2, 0)].visible, true);
+        assert_eq!(forest[(3, 0)].visible, true);
+        assert_eq!(forest[(4, 0)].visible, true);
+        assert_eq!(forest[(0, 1)].visible, true);
+        assert_eq!(forest[(1, 1)].visible, true);
+        assert_eq!(forest[(2, 1)].visible, true);
+        assert_eq!(forest[(3, 1)].visible, false);
+        assert_eq!(forest[(2, 2)].visible, false);
+        assert_eq!(forest[(2, 3)].visible, true);
+        assert_eq!(forest[(3, 3)].visible, false);
+        assert_eq!(forest[(4, 3)].visible, true);
+        assert_eq!(forest[(4, 4)].visible, true);
     }
 
     #[test]
     fn count_visible_trees() {
-        let forest = day8::parser(&TEST_INPUT);
+        let forest = day8::parser(TEST_INPUT).unwrap();
         assert_eq!(day8::count_visible_trees(&forest), 21);
     }
 
     #[test]
     fn scenic_score() {
-        let forest = day8::parser(&TEST_INPUT);
-        
-        assert_eq!(day8::scenic_score(&forest, 2, 1), 4);
-        assert_eq!(day8::scenic_score(&forest, 2, 2), 1);
-        assert_eq!(day8::scenic_score(&forest, 2, 3), 8);
-        assert_eq!(day8::scenic_score(&forest, 3, 1), 1);
-        assert_eq!(day8::scenic_score(&forest, 3, 3), 3);
-        assert_eq!(day8::scenic_score(&forest, 4, 2), 0);
-        assert_eq!(day8::scenic_score(&forest, 4, 4), 0);
+        let forest = day8::parser(TEST_INPUT).unwrap();
+
+        assert_eq!(day8::scenic_score(&forest, (1, 2), &PUZZLE), 4);
+        assert_eq!(day8::scenic_score(&forest, (2, 2), &PUZZLE), 1);
+        assert_eq!(day8::scenic_score(&forest, (3, 2), &PUZZLE), 8);
+        assert_eq!(day8::scenic_score(&forest, (1, 3), &PUZZLE), 1);
+        assert_eq!(day8::scenic_score(&forest, (3, 3), &PUZZLE), 3);
+        assert_eq!(day8::scenic_score(&forest, (2, 4), &PUZZLE), 0);
+        assert_eq!(day8::scenic_score(&forest, (4, 4), &PUZZLE), 0);
+    }
+
+    #[test]
+    fn scenic_scores() {
+        let forest = day8::parser(TEST_INPUT).unwrap();
+        let scores = day8::scenic_scores(&forest, &PUZZLE);
+        assert_eq!(scores[(3, 2)], 8);
+        assert_eq!(scores.to_string(), "00000\n01410\n06120\n01830\n00000");
+    }
+
+    /// Forest of pseudo-random heights, with long runs of equal trees when `levels` is small
+    fn random_forest(rows: usize, cols: usize, levels: u64, seed: u64) -> String {
+        let mut state = seed;
+        (0..rows)
+            .map(|_| {
+                (0..cols)
+                    .map(|_| {
+                        state = state
+                            .wrapping_mul(6364136223846793005)
+                            .wrapping_add(1442695040888963407);
+                        char::from(b'0' + (state >> 33) as u8 % levels as u8)
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Whether the tree at `pos` is visible from outside, walking out from it
+    fn visible_reference(forest: &Forest, pos: (usize, usize), sight: &LineOfSight) -> bool {
+        let height = forest[pos].height;
+        sight.directions.iter().any(|direction| {
+            let delta = direction.delta();
+            forest
+                .ray(pos, (delta.y as isize, delta.x as isize))
+                .all(|other| !sight.blocking.blocks(forest[other].height, height))
+        })
+    }
+
+    #[test]
+    fn scenic_scores_match_reference() {
+        let sights = [
+            PUZZLE,
+            LineOfSight::ALL_DIRECTIONS,
+            LineOfSight {
+                directions: &Direction8::ALL,
+                blocking: Blocking::Taller,
+            },
+            LineOfSight {
+                directions: &[Direction8::NE, Direction8::W],
+                blocking: Blocking::Taller,
+            },
+        ];
+        for (seed, (rows, cols)) in [(1, 1), (1, 9), (9, 1), (7, 13), (20, 20), (31, 4)]
+            .into_iter()
+            .enumerate()
+        {
+            for levels in [2, 10] {
+                let forest = day8::parser(&random_forest(rows, cols, levels, seed as u64)).unwrap();
+                for sight in &sights {
+                    let scores = day8::scenic_scores(&forest, sight);
+                    let visible = day8::visibility(&forest, sight);
+                    for pos in forest.positions() {
+                        assert_eq!(
+                            scores[pos],
+                            day8::scenic_score(&forest, pos, sight),
+                            "{pos:?} of {rows}x{cols} under {sight:?}"
+                        );
+                        assert_eq!(
+                            visible[pos],
+                            visible_reference(&forest, pos, sight),
+                            "{pos:?} of {rows}x{cols} under {sight:?}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn line_of_sight() {
+        let mut forest = day8::parser(TEST_INPUT).unwrap();
+        let taller = LineOfSight {
+            directions: &Direction8::ORTHOGONAL,
+            blocking: Blocking::Taller,
+        };
+        // the 3 left of it is not taller, nor is the 5 two trees up from the best tree
+        assert_eq!(day8::visibility(&forest, &taller)[(3, 1)], true);
+        assert_eq!(day8::scenic_score(&forest, (3, 2), &taller), 12);
+
+        // seen from the top right corner past the 1 and the 3 only when equal trees don't block
+        let diagonal = LineOfSight::ALL_DIRECTIONS;
+        assert_eq!(day8::visibility(&forest, &diagonal)[(2, 2)], false);
+        let diagonal_taller = LineOfSight {
+            blocking: Blocking::Taller,
+            ..diagonal
+        };
+        assert_eq!(day8::visibility(&forest, &diagonal_taller)[(2, 2)], true);
+
+        // 2 more trees towards the top right of the best tree
+        assert_eq!(day8::scenic_scores(&forest, &diagonal)[(3, 2)], 16);
+
+        day8::mark_visible(&mut forest, &diagonal_taller);
+        assert_eq!(forest[(2, 2)].visible, true);
+        assert_eq!(
+            day8::count_visible_trees(&forest),
+            day8::visibility(&forest, &diagonal_taller)
+                .iter()
+                .filter(|v| **v)
+                .count()
+        );
+        day8::mark_visible(&mut forest, &PUZZLE);
+        assert_eq!(day8::count_visible_trees(&forest), 21);
+    }
+
+    #[test]
+    fn top_scenic() {
+        let forest = day8::parser(TEST_INPUT).unwrap();
+        let scores = day8::scenic_scores(&forest, &PUZZLE);
+        assert_eq!(day8::top_scenic(&scores, 3), vec![(3, 2), (2, 1), (1, 2)]);
+        assert_eq!(day8::top_scenic(&scores, 30).len(), 25);
+        // ties in row order
+        assert_eq!(day8::top_scenic(&scores, 7)[5..], [(1, 1), (1, 3)]);
+    }
+
+    #[test]
+    fn render_heatmap() {
+        let forest = day8::parser(TEST_INPUT).unwrap();
+        let scores = day8::scenic_scores(&forest, &PUZZLE);
+        let top = day8::top_scenic(&scores, 2);
+        assert_eq!(
+            day8::render_heatmap(&forest, &Heatmap::Plain, &top),
+            "30373\n255.2\n6*.32\n3.*.9\n35390"
+        );
+
+        let heatmap = day8::render_heatmap(&forest, &Heatmap::ScenicScores(&scores), &top);
+        let rows: Vec<&str> = heatmap.lines().collect();
+        assert_eq!(rows.len(), 5);
+        assert!(rows[0].starts_with(&ansi::shade("3", 0.0)));
+        assert!(rows[1].contains(&ansi::shade("5", 0.5)));
+        assert!(rows[3].contains(&ansi::highlight("5")));
+
+        let heatmap = day8::render_heatmap(&forest, &Heatmap::Visibility, &[]);
+        assert!(heatmap.starts_with(&ansi::shade("3", 0.6 + 3.0 / 9.0 * 0.4)));
+        assert!(heatmap.contains(&ansi::shade("1", 1.0 / 9.0 * 0.4)));
+    }
+
+    #[test]
+    fn images() {
+        let forest = day8::parser(TEST_INPUT).unwrap();
+        let scores = day8::scenic_scores(&forest, &PUZZLE);
+        let pgm = day8::to_pgm(&forest, &Heatmap::ScenicScores(&scores));
+        let header = b"P5\n5 5\n255\n";
+        assert_eq!(&pgm[..header.len()], header);
+        assert_eq!(pgm.len(), header.len() + 25);
+        assert_eq!((pgm[header.len()], pgm[header.len() + 17]), (0, 255));
+        assert_eq!(pgm[header.len() + 7], 128);
+
+        let ppm = day8::to_ppm(&forest, &Heatmap::Plain, &[(3, 2)]);
+        let header = b"P6\n5 5\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        assert_eq!(ppm.len(), header.len() + 75);
+        let pixel = |idx: usize| &ppm[header.len() + 3 * idx..header.len() + 3 * idx + 3];
+        assert_eq!(pixel(17), [255, 200, 0]);
+        assert_eq!(pixel(23), [255, 255, 255]);
+        assert_eq!(pixel(24), [0, 0, 0]);
+
+        assert_eq!(
+            day8::to_pgm(&day8::parser("").unwrap(), &Heatmap::Plain),
+            b"P5\n0 0\n255\n"
+        );
+    }
+
+    #[test]
+    fn shapes() {
+        // a single row or column, every tree on the edge
+        for input in ["30373", "3\n0\n3\n7\n3"] {
+            let forest = day8::parser(input).unwrap();
+            assert_eq!(Day8::part1(&forest), "5");
+            assert_eq!(Day8::part2(&forest), "0");
+        }
+
+        let forest = day8::parser("30373\n25512\n65332").unwrap();
+        assert_eq!((forest.height(), forest.width()), (3, 5));
+        assert_eq!(forest[(1, 3)].visible, false);
+        assert_eq!(day8::count_visible_trees(&forest), 14);
+        assert_eq!(day8::scenic_score(&forest, (1, 2), &PUZZLE), 2);
+        assert_eq!(Day8::part2(&forest), "2");
+
+        let forest = day8::parser("7\n").unwrap();
+        assert_eq!(
+            (Day8::part1(&forest), Day8::part2(&forest)),
+            ("1".to_owned(), "0".to_owned())
+        );
+
+        let forest = day8::parser("").unwrap();
+        assert!(forest.is_empty());
+        assert_eq!(
+            (Day8::part1(&forest), Day8::part2(&forest)),
+            ("0".to_owned(), "0".to_owned())
+        );
+    }
+
+    #[test]
+    fn transposed() {
+        let transposed = day8::parser(TEST_INPUT)
+            .unwrap()
+            .map(|tree| tree.height)
+            .transpose()
+            .to_string();
+        let forest = day8::parser(&transposed).unwrap();
+        assert_eq!(day8::count_visible_trees(&forest), 21);
+        assert_eq!(day8::scenic_score(&forest, (2, 3), &PUZZLE), 8);
+        assert_eq!(Day8::part2(&forest), "8");
+    }
+
+    #[test]
+    fn parser_errors() {
+        let error = day8::parser("303\n2x5\n653").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.expected, "a tree height");
+
+        let error = day8::parser("303\n255\n65").unwrap_err();
+        assert_eq!(error.line, 3);
+        assert_eq!(error.expected, "a row of 3 trees");
     }
 }
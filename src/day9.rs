@@ -1,7 +1,11 @@
-use crate::helpers::read_file;
+use crate::error::{lines, ParseError};
+use crate::helpers::{Direction, Point2};
+use crate::solution::Solution;
 use std::{collections::HashSet, fmt};
 
-type Position = (i32, i32);
+type Position = Point2;
+/// (direction, distance)
+pub type Motion = (Direction, i32);
 type Knots = Vec<Knot>;
 
 #[derive(Clone)]
@@ -23,7 +27,7 @@ struct Rope {
 
 impl Rope {
     fn start(knots: u32) -> Rope {
-        let start = (0, 0);
+        let start = Point2::ZERO;
         Rope {
             knots: vec![
                 Knot {
@@ -35,17 +39,10 @@ impl Rope {
         }
     }
 
-    fn move_head(&mut self, cmd: &str) {
-        let direction = cmd.chars().nth(0).unwrap();
-        let distance = cmd[2..].parse::<i32>().unwrap();
+    fn move_head(&mut self, motion: Motion) {
+        let (direction, distance) = motion;
         for _ in 0..distance {
-            match direction {
-                'U' => self.knots[0].pos.1 += 1,
-                'D' => self.knots[0].pos.1 -= 1,
-                'L' => self.knots[0].pos.0 -= 1,
-                'R' => self.knots[0].pos.0 += 1,
-                _ => panic!("Invalid direction"),
-            }
+            self.knots[0].pos += direction.delta();
             self.pull_tail();
         }
     }
@@ -55,10 +52,8 @@ impl Rope {
             let (left, right) = self.knots.split_at_mut(i);
             let head = left.last().unwrap();
             let tail = &mut right[0];
-            let (dx, dy) = delta_pos(tail.pos, head.pos);
-            if dy.abs() > 1 || dx.abs() > 1 {
-                tail.pos.0 += 1 * dx.signum();
-                tail.pos.1 += 1 * dy.signum();
+            if tail.pos.chebyshev(head.pos) > 1 {
+                tail.pos += (head.pos - tail.pos).signum();
             }
             tail.path_set.insert(tail.pos);
         };
@@ -69,28 +64,52 @@ impl Rope {
     }
 }
 
-fn delta_pos(pos1: Position, pos2: Position) -> Position {
-    (pos2.0 - pos1.0, pos2.1 - pos1.1)
+pub fn parser(input: &str) -> Result<Vec<Motion>, ParseError> {
+    lines(9, input)
+        .map(|l| {
+            let (direction, distance) = l.split_once(l.text, " ")?;
+            let mut letters = direction.chars();
+            let direction = match (letters.next().and_then(Direction::from_letter), letters.next()) {
+                (Some(direction), None) => direction,
+                _ => return Err(l.error(direction, "`U`, `D`, `L` or `R`")),
+            };
+            Ok((direction, l.parse::<i32>(distance, "a distance")?))
+        })
+        .collect()
+}
+
+pub fn tail_visits(motions: &[Motion], knots: u32) -> usize {
+    let mut rope = Rope::start(knots);
+    for motion in motions {
+        rope.move_head(*motion);
+    }
+    rope.tail().unwrap().path_set.len()
 }
 
-pub fn solution() -> (String, String) {
-    let contents = read_file("/inputs/day9.txt");
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<Motion>;
 
-    let mut rope = Rope::start(2);
-    let mut rope_with_ten_knots = Rope::start(10);
-    for cmd in contents.lines() {
-        rope.move_head(cmd);
-        rope_with_ten_knots.move_head(cmd);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parser(input)
     }
-    let result1: usize = rope.tail().unwrap().path_set.len();
-    let result2: usize = rope_with_ten_knots.tail().unwrap().path_set.len();
 
-    return (result1.to_string(), result2.to_string());
+    fn part1(input: &Self::Input) -> String {
+        let result1: usize = tail_visits(input, 2);
+        return result1.to_string();
+    }
+
+    fn part2(input: &Self::Input) -> String {
+        let result2: usize = tail_visits(input, 10);
+        return result2.to_string();
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::day9;
+    use crate::helpers::Point2;
 
     const TEST_INPUT: &str = "\
 R 4
@@ -115,23 +134,34 @@ U 20";
     #[test]
     fn rope_part1() {
         let mut rope = day9::Rope::start(2);
-        for cmd in TEST_INPUT.lines() {
-            rope.move_head(cmd);
+        for motion in day9::parser(TEST_INPUT).unwrap() {
+            rope.move_head(motion);
         }
-        assert_eq!(rope.knots[0].pos, (2, 2));
-        assert_eq!(rope.tail().unwrap().pos, (1, 2));
+        assert_eq!(rope.knots[0].pos, Point2::new(2, -2));
+        assert_eq!(rope.tail().unwrap().pos, Point2::new(1, -2));
         assert_eq!(rope.tail().unwrap().path_set.len(), 13);
     }
 
     #[test]
     fn rope_part2() {
         let mut rope = day9::Rope::start(10);
-        for cmd in TEST_INPUT_TWO.lines() {
-            rope.move_head(cmd);
+        for motion in day9::parser(TEST_INPUT_TWO).unwrap() {
+            rope.move_head(motion);
         }
         let last_knot = rope.tail().unwrap();
-        assert_eq!(rope.knots[0].pos, (-11, 15));
-        assert_eq!(last_knot.pos, (-11, 6));
+        assert_eq!(rope.knots[0].pos, Point2::new(-11, -15));
+        assert_eq!(last_knot.pos, Point2::new(-11, -6));
         assert_eq!(last_knot.path_set.len(), 36);
     }
+
+    #[test]
+    fn parser_errors() {
+        let error = day9::parser("R 4\nX 4").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.found, "`X`");
+
+        let error = day9::parser("R 4\nU four").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.expected, "a distance");
+    }
 }
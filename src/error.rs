@@ -0,0 +1,175 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Malformed puzzle input. `line` and `column` are 1-based, a `line` past the
+/// last line of the input means the input ended too early.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl ParseError {
+    pub fn new(day: u8, line: usize, column: usize, expected: &str, found: &str) -> ParseError {
+        ParseError {
+            day,
+            line,
+            column,
+            expected: expected.to_owned(),
+            found: found.to_owned(),
+        }
+    }
+
+    /// Error for an input that ended before `expected` was found
+    pub fn end_of_input(day: u8, input: &str, expected: &str) -> ParseError {
+        ParseError::new(day, input.lines().count() + 1, 1, expected, "end of input")
+    }
+
+    /// Renders the error like a compiler diagnostic, pointing at the offending column of `input`
+    pub fn diagnostic(&self, source: &str, input: &str) -> String {
+        self.render("error", source, input)
+    }
+
+    /// Same as `diagnostic` for a problem the day skipped instead of failing on it
+    pub fn warning(&self, source: &str, input: &str) -> String {
+        self.render("warning", source, input)
+    }
+
+    fn render(&self, level: &str, source: &str, input: &str) -> String {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let mut lines = vec![
+            format!("{level}: {self}"),
+            format!("{gutter}--> {source}:{}:{}", self.line, self.column),
+        ];
+        if let Some(text) = input.lines().nth(self.line - 1) {
+            lines.push(format!("{gutter} |"));
+            lines.push(format!("{number} | {text}"));
+            lines.push(format!("{gutter} | {}^", " ".repeat(self.column - 1)));
+        }
+        lines.join("\n")
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "day {}, line {}, column {}: expected {}, found {}",
+            self.day, self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// A line of puzzle input, which turns failures on any slice of it into a located `ParseError`
+#[derive(Debug, Clone, Copy)]
+pub struct Line<'a> {
+    pub day: u8,
+    pub number: usize,
+    pub text: &'a str,
+}
+
+/// Lines of `input` with their 1-based line numbers
+pub fn lines(day: u8, input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(move |(idx, text)| Line {
+        day,
+        number: idx + 1,
+        text,
+    })
+}
+
+impl<'a> Line<'a> {
+    /// 1-based column where `token` starts, `token` being a slice of this line
+    fn column(&self, token: &str) -> usize {
+        let offset = (token.as_ptr() as usize).wrapping_sub(self.text.as_ptr() as usize);
+        if offset <= self.text.len() { offset + 1 } else { 1 }
+    }
+
+    /// Error pointing at `token`, a slice of this line
+    pub fn error(&self, token: &str, expected: &str) -> ParseError {
+        let found = if token.is_empty() { "nothing".to_owned() } else { format!("`{token}`") };
+        ParseError::new(self.day, self.number, self.column(token), expected, &found)
+    }
+
+    /// Parses `token`, a slice of this line
+    pub fn parse<T: FromStr>(&self, token: &'a str, expected: &str) -> Result<T, ParseError> {
+        token.trim().parse::<T>().map_err(|_| self.error(token.trim(), expected))
+    }
+
+    /// Splits `token`, a slice of this line, around the first `separator`
+    pub fn split_once(&self, token: &'a str, separator: &str) -> Result<(&'a str, &'a str), ParseError> {
+        token
+            .split_once(separator)
+            .ok_or_else(|| self.error(token, &format!("`{separator}`")))
+    }
+
+    /// Rest of the line after `prefix`
+    pub fn strip_prefix(&self, prefix: &str) -> Result<&'a str, ParseError> {
+        self.text
+            .strip_prefix(prefix)
+            .ok_or_else(|| self.error(self.text, &format!("`{prefix}`")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{self, ParseError};
+
+    const TEST_INPUT: &str = "\
+2-4,6-8
+2-x,6-8";
+
+    #[test]
+    fn line_errors() {
+        let line = error::lines(4, TEST_INPUT).nth(1).unwrap();
+        assert_eq!(line.number, 2);
+
+        let (range, _) = line.split_once(line.text, ",").unwrap();
+        let (_, end) = line.split_once(range, "-").unwrap();
+        assert_eq!(
+            line.parse::<u32>(end, "a section number"),
+            Err(ParseError::new(4, 2, 3, "a section number", "`x`"))
+        );
+        assert_eq!(
+            line.split_once(end, "-"),
+            Err(ParseError::new(4, 2, 3, "`-`", "`x`"))
+        );
+        assert_eq!(
+            line.strip_prefix("move").unwrap_err().to_string(),
+            "day 4, line 2, column 1: expected `move`, found `2-x,6-8`"
+        );
+    }
+
+    #[test]
+    fn diagnostic() {
+        let error = ParseError::new(4, 2, 3, "a section number", "`x`");
+        assert_eq!(
+            error.diagnostic("inputs/day4.txt", TEST_INPUT),
+            "\
+error: day 4, line 2, column 3: expected a section number, found `x`
+ --> inputs/day4.txt:2:3
+  |
+2 | 2-x,6-8
+  |   ^"
+        );
+
+        assert!(error
+            .warning("inputs/day4.txt", TEST_INPUT)
+            .starts_with("warning: day 4, line 2, column 3: expected a section number"));
+
+        let error = ParseError::end_of_input(13, TEST_INPUT, "a right packet");
+        assert_eq!(error.line, 3);
+        assert_eq!(
+            error.diagnostic("-", TEST_INPUT),
+            "\
+error: day 13, line 3, column 1: expected a right packet, found end of input
+ --> -:3:1"
+        );
+    }
+}
@@ -1,11 +1,69 @@
+pub mod ansi;
+pub mod grid;
+pub mod point;
+pub mod search;
+
+pub use grid::Grid;
+pub use point::{Direction, Direction8, Point2};
+
 use std::env;
 use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Environment variable overriding the folder with the puzzle inputs
+pub const INPUT_DIR_ENV: &str = "AOC22_INPUT_DIR";
+
+/// Folder with the `dayN.txt` inputs: `dir` if given, then `$AOC22_INPUT_DIR`, then `./inputs`
+pub fn input_dir(dir: Option<&Path>) -> PathBuf {
+    match (dir, env::var_os(INPUT_DIR_ENV)) {
+        (Some(dir), _) => dir.to_path_buf(),
+        (None, Some(dir)) => PathBuf::from(dir),
+        (None, None) => PathBuf::from("inputs"),
+    }
+}
+
+pub fn input_path(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("day{day}.txt"))
+}
+
+/// Reads a puzzle input, `-` meaning stdin.
+/// Stdin is only consumed once, so repeated runs get the same contents.
+pub fn read_input(path: &Path) -> Result<String, String> {
+    static STDIN: OnceLock<Result<String, String>> = OnceLock::new();
+
+    if path == Path::new("-") {
+        return STDIN
+            .get_or_init(|| {
+                let mut contents = String::new();
+                io::stdin()
+                    .read_to_string(&mut contents)
+                    .map(|_| contents)
+                    .map_err(|e| format!("cannot read stdin: {e}"))
+            })
+            .clone();
+    }
+    fs::read_to_string(path).map_err(|e| format!("cannot read {}: {e}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::helpers;
+    use std::path::{Path, PathBuf};
 
+    #[test]
+    fn input_path() {
+        let dir = helpers::input_dir(Some(Path::new("/tmp/alice")));
+        assert_eq!(dir, PathBuf::from("/tmp/alice"));
+        assert_eq!(helpers::input_path(&dir, 7), PathBuf::from("/tmp/alice/day7.txt"));
+    }
 
-pub fn read_file(input_path: &str) -> String {
-    let binding = env::current_dir().unwrap();
-    let cur_dir = binding.as_path().to_str().unwrap();
-    let file_path = cur_dir.to_owned() + input_path;
-    let contents = fs::read_to_string(file_path.clone()).expect(format!("Should have been able to read the file --> {file_path}").as_str());
-    return contents;
+    #[test]
+    fn read_input() {
+        let contents = helpers::read_input(Path::new("inputs/day10_test.txt")).unwrap();
+        assert!(contents.starts_with("addx 15"));
+        let error = helpers::read_input(Path::new("inputs/day99.txt")).unwrap_err();
+        assert!(error.starts_with("cannot read inputs/day99.txt"));
+    }
 }
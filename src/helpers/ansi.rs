@@ -0,0 +1,34 @@
+//! ANSI escape sequences for coloured terminal output
+
+/// Ends any colouring started before
+pub const RESET: &str = "\x1b[0m";
+
+/// `text` over a gray background, `level` going from 0.0 for black to 1.0 for white. The text is
+/// written in black or white, whichever reads better on that gray.
+pub fn shade(text: &str, level: f64) -> String {
+    let gray = 232 + (level.clamp(0.0, 1.0) * 23.0).round() as u8;
+    let foreground = if gray < 244 { 97 } else { 30 };
+    format!("\x1b[{foreground};48;5;{gray}m{text}{RESET}")
+}
+
+/// `text` in bold black over yellow, standing out among shaded text
+pub fn highlight(text: &str) -> String {
+    format!("\x1b[1;30;43m{text}{RESET}")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::helpers::ansi;
+
+    #[test]
+    fn shade() {
+        assert_eq!(ansi::shade("a", 0.0), "\x1b[97;48;5;232ma\x1b[0m");
+        assert_eq!(ansi::shade("z", 1.0), "\x1b[30;48;5;255mz\x1b[0m");
+        assert_eq!(ansi::shade("z", 7.0), ansi::shade("z", 1.0));
+    }
+
+    #[test]
+    fn highlight() {
+        assert_eq!(ansi::highlight("8"), "\x1b[1;30;43m8\x1b[0m");
+    }
+}
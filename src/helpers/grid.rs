@@ -0,0 +1,318 @@
+use crate::error::{lines, Line, ParseError};
+use crate::helpers::point::Point2;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// Steps to the 4 orthogonal neighbors as `(row, col)` offsets, clockwise from up
+pub const NEIGHBORS4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// Steps to the 8 surrounding neighbors as `(row, col)` offsets, clockwise from up
+pub const NEIGHBORS8: [(isize, isize); 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+/// Rectangular grid stored row by row. Cells are always indexed `(row, col)`, row 0 being the
+/// first line of the input.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Grid<T> {
+    height: usize,
+    width: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(height: usize, width: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            height,
+            width,
+            cells: vec![value; height * width],
+        }
+    }
+
+    /// Grid from its rows, `None` if they don't all have the same length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Grid<T>> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+        let cells = rows.into_iter().flatten().collect();
+        Some(Grid { height, width, cells })
+    }
+
+    /// Parses a map with one character per cell. `cell` gets the line and the character as a
+    /// slice of it, so its errors point at the right column. Every row must be as long as the
+    /// first one, otherwise the error expects "a row of N `unit`".
+    pub fn parse<'a, F>(day: u8, input: &'a str, unit: &str, mut cell: F) -> Result<Grid<T>, ParseError>
+    where
+        F: FnMut(&Line<'a>, &'a str) -> Result<T, ParseError>,
+    {
+        let mut grid = Grid {
+            height: 0,
+            width: 0,
+            cells: Vec::new(),
+        };
+        for line in lines(day, input) {
+            let width = line.text.chars().count();
+            if grid.height == 0 {
+                grid.width = width;
+            } else if width != grid.width {
+                return Err(line.error(line.text, &format!("a row of {} {unit}", grid.width)));
+            }
+            for (idx, c) in line.text.char_indices() {
+                grid.cells.push(cell(&line, &line.text[idx..idx + c.len_utf8()])?);
+            }
+            grid.height += 1;
+        }
+        Ok(grid)
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, (row, col): (usize, usize)) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, pos: (usize, usize)) -> Option<&T> {
+        if self.contains(pos) { Some(&self[pos]) } else { None }
+    }
+
+    pub fn get_mut(&mut self, pos: (usize, usize)) -> Option<&mut T> {
+        if self.contains(pos) { Some(&mut self[pos]) } else { None }
+    }
+
+    /// Cell at `point`, `None` outside the grid
+    pub fn get_point(&self, point: Point2) -> Option<&T> {
+        self.get(point.to_index()?)
+    }
+
+    /// Cells in row order
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    /// Every `(row, col)` in row order
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |idx| (idx / width, idx % width))
+    }
+
+    /// Cells with their `(row, col)`, in row order
+    pub fn enumerate(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// First `(row, col)` in row order whose cell matches `predicate`
+    pub fn position<P: FnMut(&T) -> bool>(&self, mut predicate: P) -> Option<(usize, usize)> {
+        self.enumerate().find(|(_, cell)| predicate(cell)).map(|(pos, _)| pos)
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|row| self.row(row))
+    }
+
+    /// Cells of column `col`, top to bottom
+    pub fn column(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(col < self.width, "column {col} out of a grid {} wide", self.width);
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    /// `pos` moved by `step`, if still inside the grid
+    pub fn offset(&self, (row, col): (usize, usize), (d_row, d_col): (isize, isize)) -> Option<(usize, usize)> {
+        let next = (row.checked_add_signed(d_row)?, col.checked_add_signed(d_col)?);
+        if self.contains(next) { Some(next) } else { None }
+    }
+
+    /// Orthogonal neighbors of `pos` inside the grid, clockwise from up
+    pub fn neighbors4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        NEIGHBORS4.iter().filter_map(move |step| self.offset(pos, *step))
+    }
+
+    /// Orthogonal and diagonal neighbors of `pos` inside the grid, clockwise from up
+    pub fn neighbors8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        NEIGHBORS8.iter().filter_map(move |step| self.offset(pos, *step))
+    }
+
+    /// Positions met walking from `pos` (excluded) by `step` until leaving the grid
+    pub fn ray(&self, pos: (usize, usize), step: (isize, isize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let mut curr = Some(pos);
+        std::iter::from_fn(move || {
+            curr = self.offset(curr?, step).filter(|_| step != (0, 0));
+            curr
+        })
+    }
+
+    /// Grid with rows and columns swapped
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            height: self.width,
+            width: self.height,
+            cells: (0..self.width)
+                .flat_map(|col| self.column(col).cloned())
+                .collect(),
+        }
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            height: self.height,
+            width: self.width,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        assert!(self.contains((row, col)), "({row}, {col}) out of a {}x{} grid", self.height, self.width);
+        &self.cells[row * self.width + col]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        assert!(self.contains((row, col)), "({row}, {col}) out of a {}x{} grid", self.height, self.width);
+        &mut self.cells[row * self.width + col]
+    }
+}
+
+impl<T> Index<Point2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point2) -> &T {
+        self.get_point(point)
+            .unwrap_or_else(|| panic!("{point} out of a {}x{} grid", self.height, self.width))
+    }
+}
+
+impl<T> IndexMut<Point2> for Grid<T> {
+    fn index_mut(&mut self, point: Point2) -> &mut T {
+        let (height, width) = (self.height, self.width);
+        point
+            .to_index()
+            .and_then(|pos| self.get_mut(pos))
+            .unwrap_or_else(|| panic!("{point} out of a {height}x{width} grid"))
+    }
+}
+
+/// One line per row, cells written next to each other
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (idx, row) in self.rows().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::helpers::grid::Grid;
+    use crate::helpers::point::Point2;
+
+    const TEST_INPUT: &str = "\
+abc
+def";
+
+    fn grid() -> Grid<char> {
+        Grid::parse(0, TEST_INPUT, "letters", |line, c| line.parse(c, "a letter")).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let grid = grid();
+        assert_eq!((grid.height(), grid.width()), (2, 3));
+        assert_eq!(grid[(0, 2)], 'c');
+        assert_eq!(grid[(1, 0)], 'd');
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid[Point2::new(2, 0)], 'c');
+        assert_eq!(grid.get_point(Point2::new(0, -1)), None);
+        assert_eq!(grid.position(|&c| c == 'e'), Some((1, 1)));
+        assert_eq!(grid.to_string(), TEST_INPUT);
+        assert!(Grid::<char>::parse(0, "", "letters", |line, c| line.parse(c, "a letter")).unwrap().is_empty());
+
+        let error = Grid::<u8>::parse(0, "12\n3", "digits", |line, c| line.parse(c, "a digit")).unwrap_err();
+        assert_eq!((error.line, error.expected.as_str()), (2, "a row of 2 digits"));
+        let error = Grid::<u8>::parse(0, "12\n3x", "digits", |line, c| line.parse(c, "a digit")).unwrap_err();
+        assert_eq!((error.line, error.column, error.found.as_str()), (2, 2, "`x`"));
+    }
+
+    #[test]
+    fn from_rows() {
+        let grid = Grid::from_rows(vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f']]).unwrap();
+        assert_eq!(grid, self::grid());
+        assert_eq!(Grid::from_rows(vec![vec![1, 2], vec![3]]), None);
+        assert_eq!(Grid::new(2, 3, '.').to_string(), "...\n...");
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = grid();
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.column(2).rev().collect::<String>(), "fc");
+        assert_eq!(grid.positions().collect::<Vec<_>>(), vec![(0, 0), (0, 1), (0, 2), (1, 0), (1, 1), (1, 2)]);
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.map(|c| c.to_ascii_uppercase()).to_string(), "ABC\nDEF");
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = grid();
+        assert_eq!(grid.neighbors4((0, 0)).collect::<Vec<_>>(), vec![(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbors4((1, 1)).collect::<Vec<_>>(), vec![(0, 1), (1, 2), (1, 0)]);
+        assert_eq!(
+            grid.neighbors8((1, 1)).collect::<Vec<_>>(),
+            vec![(0, 1), (0, 2), (1, 2), (1, 0), (0, 0)]
+        );
+        assert_eq!(grid.offset((0, 2), (0, 1)), None);
+    }
+
+    #[test]
+    fn ray() {
+        let grid = grid();
+        assert_eq!(grid.ray((0, 0), (0, 1)).collect::<Vec<_>>(), vec![(0, 1), (0, 2)]);
+        assert_eq!(grid.ray((1, 2), (-1, -1)).collect::<Vec<_>>(), vec![(0, 1)]);
+        assert_eq!(grid.ray((1, 2), (0, 1)).count(), 0);
+        assert_eq!(grid.ray((1, 2), (0, 0)).count(), 0);
+    }
+}
@@ -0,0 +1,318 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// Point or vector on the plane. `y` grows downwards like the rows of a `Grid`, so the grid
+/// index `(row, col)` is the point `(col, row)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point2 {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point2 {
+    pub const ZERO: Point2 = Point2 { x: 0, y: 0 };
+
+    pub const fn new(x: i32, y: i32) -> Point2 {
+        Point2 { x, y }
+    }
+
+    /// Point of the grid index `(row, col)`
+    pub fn from_index((row, col): (usize, usize)) -> Point2 {
+        Point2::new(col as i32, row as i32)
+    }
+
+    /// Grid index `(row, col)` of the point, `None` left of or above the grid
+    pub fn to_index(self) -> Option<(usize, usize)> {
+        Some((usize::try_from(self.y).ok()?, usize::try_from(self.x).ok()?))
+    }
+
+    /// Unit step towards the point on both axes, each coordinate being -1, 0 or 1
+    pub fn signum(self) -> Point2 {
+        Point2::new(self.x.signum(), self.y.signum())
+    }
+
+    /// Distance moving along the axes only
+    pub fn manhattan(self, other: Point2) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// Distance moving along the axes and diagonals, like a king on a chessboard
+    pub fn chebyshev(self, other: Point2) -> i32 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+}
+
+impl fmt::Display for Point2 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Add for Point2 {
+    type Output = Point2;
+
+    fn add(self, other: Point2) -> Point2 {
+        Point2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point2 {
+    type Output = Point2;
+
+    fn sub(self, other: Point2) -> Point2 {
+        Point2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl AddAssign for Point2 {
+    fn add_assign(&mut self, other: Point2) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Point2 {
+    fn sub_assign(&mut self, other: Point2) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Point2 {
+    type Output = Point2;
+
+    fn neg(self) -> Point2 {
+        Point2::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i32> for Point2 {
+    type Output = Point2;
+
+    fn mul(self, factor: i32) -> Point2 {
+        Point2::new(self.x * factor, self.y * factor)
+    }
+}
+
+/// One of the 4 orthogonal directions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Clockwise from up
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// Direction of a `U`, `R`, `D` or `L` letter
+    pub fn from_letter(letter: char) -> Option<Direction> {
+        match letter {
+            'U' => Some(Direction::Up),
+            'R' => Some(Direction::Right),
+            'D' => Some(Direction::Down),
+            'L' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    /// Unit step in this direction
+    pub fn delta(self) -> Point2 {
+        match self {
+            Direction::Up => Point2::new(0, -1),
+            Direction::Right => Point2::new(1, 0),
+            Direction::Down => Point2::new(0, 1),
+            Direction::Left => Point2::new(-1, 0),
+        }
+    }
+
+    /// Direction of a unit step, `None` for any other vector
+    pub fn from_delta(delta: Point2) -> Option<Direction> {
+        Direction::ALL
+            .into_iter()
+            .find(|direction| direction.delta() == delta)
+    }
+
+    /// `^`, `>`, `v` or `<`, the way the puzzles draw paths
+    pub fn arrow(self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+        }
+    }
+
+    pub fn turn_right(self) -> Direction {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Direction {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Direction {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+}
+
+/// One of the 8 compass directions, diagonals included
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction8 {
+    /// Clockwise from north
+    pub const ALL: [Direction8; 8] = [
+        Direction8::N,
+        Direction8::NE,
+        Direction8::E,
+        Direction8::SE,
+        Direction8::S,
+        Direction8::SW,
+        Direction8::W,
+        Direction8::NW,
+    ];
+
+    /// The 4 orthogonal directions, clockwise from north
+    pub const ORTHOGONAL: [Direction8; 4] =
+        [Direction8::N, Direction8::E, Direction8::S, Direction8::W];
+
+    /// Unit step in this direction, a diagonal moving on both axes
+    pub fn delta(self) -> Point2 {
+        match self {
+            Direction8::N => Point2::new(0, -1),
+            Direction8::NE => Point2::new(1, -1),
+            Direction8::E => Point2::new(1, 0),
+            Direction8::SE => Point2::new(1, 1),
+            Direction8::S => Point2::new(0, 1),
+            Direction8::SW => Point2::new(-1, 1),
+            Direction8::W => Point2::new(-1, 0),
+            Direction8::NW => Point2::new(-1, -1),
+        }
+    }
+
+    /// Direction of a unit step, diagonals included, `None` for any other vector
+    pub fn from_delta(delta: Point2) -> Option<Direction8> {
+        Direction8::ALL
+            .into_iter()
+            .find(|direction| direction.delta() == delta)
+    }
+
+    /// Same arrows as `Direction` for the orthogonal directions, `↗`, `↘`, `↙` or `↖` for the diagonals
+    pub fn arrow(self) -> char {
+        match self {
+            Direction8::NE => '↗',
+            Direction8::SE => '↘',
+            Direction8::SW => '↙',
+            Direction8::NW => '↖',
+            _ => Direction::ALL[self as usize / 2].arrow(),
+        }
+    }
+
+    /// 45 degrees clockwise
+    pub fn turn_right(self) -> Direction8 {
+        Direction8::ALL[(self as usize + 1) % 8]
+    }
+
+    /// 45 degrees counterclockwise
+    pub fn turn_left(self) -> Direction8 {
+        Direction8::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn reverse(self) -> Direction8 {
+        Direction8::ALL[(self as usize + 4) % 8]
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Direction8 {
+        Direction8::ALL[direction as usize * 2]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::helpers::point::{Direction, Direction8, Point2};
+
+    #[test]
+    fn arithmetic() {
+        let a = Point2::new(3, -2);
+        let b = Point2::new(-1, 4);
+        assert_eq!(a + b, Point2::new(2, 2));
+        assert_eq!(a - b, Point2::new(4, -6));
+        assert_eq!(-a, Point2::new(-3, 2));
+        assert_eq!(a * 2, Point2::new(6, -4));
+        assert_eq!((a - b).signum(), Point2::new(1, -1));
+        assert_eq!(a.manhattan(b), 10);
+        assert_eq!(a.chebyshev(b), 6);
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+        assert_eq!(c.to_string(), "(-1, 4)");
+    }
+
+    #[test]
+    fn grid_index() {
+        assert_eq!(Point2::from_index((2, 5)), Point2::new(5, 2));
+        assert_eq!(Point2::new(5, 2).to_index(), Some((2, 5)));
+        assert_eq!(Point2::new(-1, 2).to_index(), None);
+        assert_eq!((Point2::ZERO + Direction::Up.delta()).to_index(), None);
+    }
+
+    #[test]
+    fn directions() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.reverse(), Direction::Right);
+        assert_eq!(Direction::from_letter('D'), Some(Direction::Down));
+        assert_eq!(Direction::from_letter('X'), None);
+        assert_eq!(
+            Direction::from_delta(Point2::new(-1, 0)),
+            Some(Direction::Left)
+        );
+        assert_eq!(Direction::from_delta(Point2::new(1, 1)), None);
+        assert_eq!(Direction::Down.arrow(), 'v');
+        for direction in Direction::ALL {
+            assert_eq!(
+                direction.delta() + direction.reverse().delta(),
+                Point2::ZERO
+            );
+            assert_eq!(Direction8::from(direction).delta(), direction.delta());
+        }
+
+        assert_eq!(Direction8::N.turn_right(), Direction8::NE);
+        assert_eq!(Direction8::N.turn_left(), Direction8::NW);
+        assert_eq!(Direction8::SW.reverse(), Direction8::NE);
+        assert!(Direction8::SE.is_diagonal());
+        assert_eq!(
+            Direction8::from_delta(Point2::new(-1, 1)),
+            Some(Direction8::SW)
+        );
+        assert_eq!(Direction8::W.arrow(), '<');
+        assert_eq!(Direction8::SW.arrow(), '↙');
+        assert_eq!(
+            Direction8::SE.delta(),
+            Direction::Down.delta() + Direction::Right.delta()
+        );
+    }
+}
@@ -0,0 +1,284 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Nodes seen by a search, each with the node it was reached from
+struct Visited<N> {
+    nodes: Vec<N>,
+    parents: Vec<usize>,
+    index: HashMap<N, usize>,
+}
+
+impl<N: Eq + Hash + Clone> Visited<N> {
+    fn new(start: N) -> Visited<N> {
+        Visited {
+            nodes: vec![start.clone()],
+            parents: vec![0],
+            index: HashMap::from([(start, 0)]),
+        }
+    }
+
+    /// Index of `node`, and whether it was just added with `parent`
+    fn insert(&mut self, node: N, parent: usize) -> (usize, bool) {
+        if let Some(&idx) = self.index.get(&node) {
+            return (idx, false);
+        }
+        let idx = self.nodes.len();
+        self.nodes.push(node.clone());
+        self.parents.push(parent);
+        self.index.insert(node, idx);
+        (idx, true)
+    }
+
+    /// Nodes from the start to `idx`, both included
+    fn path(&self, mut idx: usize) -> Vec<N> {
+        let mut path = vec![self.nodes[idx].clone()];
+        while idx != 0 {
+            idx = self.parents[idx];
+            path.push(self.nodes[idx].clone());
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// Breadth first search for graphs where every step costs 1. Returns the path from `start` to
+/// the nearest node matching `goal`, both ends included, and its number of steps.
+pub fn bfs<N, S, I, G>(start: N, mut successors: S, mut goal: G) -> Option<(Vec<N>, usize)>
+where
+    N: Eq + Hash + Clone,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+    G: FnMut(&N) -> bool,
+{
+    let mut visited = Visited::new(start);
+    let mut queue = VecDeque::from([0]);
+    while let Some(idx) = queue.pop_front() {
+        if goal(&visited.nodes[idx]) {
+            let path = visited.path(idx);
+            let steps = path.len() - 1;
+            return Some((path, steps));
+        }
+        for next in successors(&visited.nodes[idx]) {
+            if let (next_idx, true) = visited.insert(next, idx) {
+                queue.push_back(next_idx);
+            }
+        }
+    }
+    None
+}
+
+/// Breadth first search of every node reachable from `start`, with its number of steps from
+/// `start`
+pub fn bfs_distances<N, S, I>(start: N, mut successors: S) -> HashMap<N, usize>
+where
+    N: Eq + Hash + Clone,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((node, steps)) = queue.pop_front() {
+        for next in successors(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), steps + 1);
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+    distances
+}
+
+/// Dijkstra's search. `successors` gives the nodes reachable from a node with the cost of each
+/// edge, costs must not be negative. Returns the cheapest path from `start` to a node matching
+/// `goal`, both ends included, and its total cost.
+pub fn dijkstra<N, C, S, I, G>(start: N, successors: S, goal: G) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    G: FnMut(&N) -> bool,
+{
+    astar(start, successors, goal, |_| C::default())
+}
+
+/// Dijkstra's search of every node reachable from `start`, with the cost of the cheapest path
+/// from `start` to it
+pub fn dijkstra_distances<N, C, S, I>(start: N, mut successors: S) -> HashMap<N, C>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Copy + Ord + Default + Add<Output = C>,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut distances = HashMap::from([(start.clone(), C::default())]);
+    let mut heap = BinaryHeap::from([Reverse((C::default(), start))]);
+    while let Some(Reverse((cost, node))) = heap.pop() {
+        if cost > distances[&node] {
+            continue;
+        }
+        for (next, edge) in successors(&node) {
+            let next_cost = cost + edge;
+            if distances.get(&next).is_none_or(|known| next_cost < *known) {
+                distances.insert(next.clone(), next_cost);
+                heap.push(Reverse((next_cost, next)));
+            }
+        }
+    }
+    distances
+}
+
+/// A* search: Dijkstra's search guided by `heuristic`, an estimate of the cost left from a node
+/// to the goal. The path found is the cheapest as long as the estimate never exceeds the real
+/// cost.
+pub fn astar<N, C, S, I, G, H>(
+    start: N,
+    mut successors: S,
+    mut goal: G,
+    mut heuristic: H,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    G: FnMut(&N) -> bool,
+    H: FnMut(&N) -> C,
+{
+    let zero = C::default();
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), zero, 0))]);
+    let mut visited = Visited::new(start);
+    let mut costs = vec![zero];
+    while let Some(Reverse((_, cost, idx))) = heap.pop() {
+        // a cheaper way to this node was queued after this one
+        if cost > costs[idx] {
+            continue;
+        }
+        if goal(&visited.nodes[idx]) {
+            return Some((visited.path(idx), cost));
+        }
+        for (next, edge) in successors(&visited.nodes[idx]) {
+            let next_cost = cost + edge;
+            let estimate = next_cost + heuristic(&next);
+            let (next_idx, new) = visited.insert(next, idx);
+            if new {
+                costs.push(next_cost);
+            } else if next_cost < costs[next_idx] {
+                costs[next_idx] = next_cost;
+                visited.parents[next_idx] = idx;
+            } else {
+                continue;
+            }
+            heap.push(Reverse((estimate, next_cost, next_idx)));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::helpers::search;
+    use crate::helpers::{Grid, Point2};
+    use std::collections::HashMap;
+
+    const MAZE: &str = "\
+S.#.....
+.##.###.
+....#..G
+.##...#.";
+
+    fn maze() -> (Grid<char>, Point2, Point2) {
+        let grid = Grid::parse(0, MAZE, "cells", |line, c| line.parse(c, "a cell")).unwrap();
+        let start = Point2::from_index(grid.position(|&c| c == 'S').unwrap());
+        let end = Point2::from_index(grid.position(|&c| c == 'G').unwrap());
+        (grid, start, end)
+    }
+
+    fn moves(grid: &Grid<char>, pos: Point2) -> Vec<Point2> {
+        crate::helpers::Direction::ALL
+            .iter()
+            .map(|direction| pos + direction.delta())
+            .filter(|next| matches!(grid.get_point(*next), Some(c) if *c != '#'))
+            .collect()
+    }
+
+    #[test]
+    fn bfs() {
+        let (grid, start, end) = maze();
+        let (path, steps) =
+            search::bfs(start, |pos| moves(&grid, *pos), |pos| *pos == end).unwrap();
+        assert_eq!(steps, 11);
+        assert_eq!(path.len(), 12);
+        assert_eq!((path[0], path[11]), (start, end));
+        assert!(path.windows(2).all(|step| step[0].manhattan(step[1]) == 1));
+
+        assert_eq!(
+            search::bfs(start, |pos| moves(&grid, *pos), |_| true),
+            Some((vec![start], 0))
+        );
+        assert_eq!(
+            search::bfs(start, |pos| moves(&grid, *pos), |pos| pos.x > 10),
+            None
+        );
+    }
+
+    #[test]
+    fn bfs_distances() {
+        let (grid, start, end) = maze();
+        let distances = search::bfs_distances(start, |pos| moves(&grid, *pos));
+        assert_eq!(distances[&start], 0);
+        assert_eq!(distances[&end], 11);
+        assert_eq!(distances.len(), grid.iter().filter(|c| **c != '#').count());
+    }
+
+    #[test]
+    fn dijkstra() {
+        // going around through c is cheaper than the direct edge
+        let edges = |node: &char| match node {
+            'a' => vec![('b', 10), ('c', 1)],
+            'c' => vec![('d', 2), ('a', 1)],
+            'd' => vec![('b', 3)],
+            _ => vec![],
+        };
+        assert_eq!(
+            search::dijkstra('a', edges, |n| *n == 'b'),
+            Some((vec!['a', 'c', 'd', 'b'], 6))
+        );
+        assert_eq!(search::dijkstra('a', edges, |n| *n == 'e'), None);
+        assert_eq!(search::dijkstra('b', edges, |n| *n == 'a'), None);
+    }
+
+    #[test]
+    fn dijkstra_distances() {
+        let edges = |node: &char| match node {
+            'a' => vec![('b', 10), ('c', 1)],
+            'c' => vec![('d', 2), ('a', 1)],
+            'd' => vec![('b', 3)],
+            _ => vec![],
+        };
+        let distances = search::dijkstra_distances('a', edges);
+        assert_eq!(
+            distances,
+            HashMap::from([('a', 0), ('b', 6), ('c', 1), ('d', 3)])
+        );
+    }
+
+    #[test]
+    fn astar() {
+        let (grid, start, end) = maze();
+        let successors = |pos: &Point2| moves(&grid, *pos).into_iter().map(|next| (next, 1));
+        let (path, cost) = search::astar(
+            start,
+            successors,
+            |pos| *pos == end,
+            |pos| pos.manhattan(end),
+        )
+        .unwrap();
+        assert_eq!(cost, 11);
+        assert_eq!(path.last(), Some(&end));
+        let (_, cost) = search::dijkstra(start, successors, |pos| *pos == end).unwrap();
+        assert_eq!(cost, 11);
+    }
+}
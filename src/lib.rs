@@ -0,0 +1,41 @@
+//! Advent of Code 2022 solutions as a library.
+//!
+//! Every day lives in its own `dayN` module with its parser and solving functions, and implements
+//! [`Solution`] through a `DayN` struct. [`DAYS`] lists them all for runners, the `aoc22` binary
+//! being one of them.
+//!
+//! ```
+//! use aoc22::{day12, Solution};
+//!
+//! let input = day12::Day12::parse("SbcdefghijklmnopqrstuvwxyE").unwrap();
+//! assert_eq!(day12::Day12::part1(&input), "25");
+//! ```
+#![allow(clippy::needless_return)]
+
+pub mod answers;
+pub mod cli;
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod day10;
+#[cfg(test)]
+mod day10_test;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod error;
+pub mod helpers;
+pub mod output;
+pub mod registry;
+pub mod runner;
+pub mod solution;
+
+pub use error::ParseError;
+pub use registry::DAYS;
+pub use solution::{Puzzle, Solution};
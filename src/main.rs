@@ -1,85 +1,86 @@
-pub mod day1;
-pub mod day2;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
-pub mod day7;
-pub mod day8;
-pub mod day9;
-pub mod day10;
-pub mod day10_test;
-pub mod day11;
-pub mod day12;
-pub mod day13;
-pub mod helpers;
+use aoc22::helpers::{input_dir, input_path, read_input};
+use aoc22::output::{self, Format};
+use aoc22::{answers, cli, runner, DAYS};
+use std::env;
+use std::process;
 
 fn main() {
-    println!("Hello, AOC 2022!");
-    println!("---");
+    let all_days: Vec<u8> = DAYS.iter().map(|(day, _)| *day).collect();
+    let args = match cli::parse_args(env::args().skip(1), &all_days) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("error: {e}\n\n{}", cli::USAGE);
+            process::exit(cli::EXIT_USAGE);
+        }
+    };
+    if args.help {
+        println!("{}", cli::USAGE);
+        return;
+    }
 
-    let day1_result = day1::solution();
-    println!("Day 1, Part 1 Answer: {}", day1_result.0);
-    println!("Day 1, Part 2 Answer: {}", day1_result.1);
-    println!("---");
+    let input_dir = input_dir(args.input_dir.as_deref());
+    let answers = if args.verify {
+        let answers_path = input_dir.join("answers.toml");
+        match read_input(&answers_path).and_then(|contents| answers::parse(&contents)) {
+            Ok(answers) => Some(answers),
+            Err(e) => {
+                eprintln!("error: {}: {e}", answers_path.display());
+                process::exit(cli::EXIT_NO_ANSWERS);
+            }
+        }
+    } else {
+        None
+    };
 
-    let day2_result = day2::solution();
-    println!("Day 2, Part 1 Answer: {}", day2_result.0);
-    println!("Day 2, Part 2 Answer: {}", day2_result.1);
-    println!("---");
+    let text = args.format == Format::Text && answers.is_none();
+    if text {
+        println!("Hello, AOC 2022!");
+        println!("---");
+    }
 
-    let day3_result = day3::solution();
-    println!("Day 3, Part 1 Answer: {}", day3_result.0);
-    println!("Day 3, Part 2 Answer: {}", day3_result.1);
-    println!("---");
+    runner::install_panic_hook();
+    let parts = args.parts();
+    let mut reports: Vec<runner::DayReport> = Vec::new();
+    for (day, puzzle) in DAYS.iter().filter(|(day, _)| args.days.contains(day)) {
+        let path = args.inputs.get(day).cloned().unwrap_or(input_path(&input_dir, *day));
+        let report = runner::run_isolated(*day, *puzzle, &parts, &path, args.repeat);
+        // parse errors come with a diagnostic that doesn't fit in the report line
+        match &report.outcome {
+            Err(e) if e.contains('\n') => eprintln!("{e}"),
+            Ok(day_runs) => day_runs[0].warnings.iter().for_each(|w| eprintln!("{w}")),
+            Err(_) => (),
+        }
+        if text {
+            match &report.outcome {
+                Ok(day_runs) => {
+                    for part in &day_runs[0].parts {
+                        println!("{}", output::text_answer(*day, part.part, &part.answer));
+                    }
+                }
+                Err(_) => println!("Day {day}: FAILED ({})", report.error_summary().unwrap_or_default()),
+            }
+            println!("---");
+        }
+        reports.push(report);
+    }
 
-    let day4_result = day4::solution();
-    println!("Day 4, Part 1 Answer: {}", day4_result.0);
-    println!("Day 4, Part 2 Answer: {}", day4_result.1);
-    println!("---");
-
-    let day5_result = day5::solution();
-    println!("Day 5, Part 1 Answer: {}", day5_result.0);
-    println!("Day 5, Part 2 Answer: {}", day5_result.1);
-    println!("---");
-
-    let day6_result = day6::solution();
-    println!("Day 6, Part 1 Answer: {}", day6_result.0);
-    println!("Day 6, Part 2 Answer: {}", day6_result.1);
-    println!("---");
-
-    let day7_result = day7::solution();
-    println!("Day 7, Part 1 Answer: {}", day7_result.0);
-    println!("Day 7, Part 2 Answer: {}", day7_result.1);
-    println!("---");
-
-    let day8_result = day8::solution();
-    println!("Day 8, Part 1 Answer: {}", day8_result.0);
-    println!("Day 8, Part 2 Answer: {}", day8_result.1);
-    println!("---");
-
-    let day9_result = day9::solution();
-    println!("Day 9, Part 1 Answer: {}", day9_result.0);
-    println!("Day 9, Part 2 Answer: {}", day9_result.1);
-    println!("---");
-
-    let day10_result = day10::solution();
-    println!("Day 10, Part 1 Answer: {}", day10_result.0);
-    println!("Day 10, Part 2 Answer: {}", day10_result.1);
-    println!("---");
-
-    let day11_result = day11::solution();
-    println!("Day 11, Part 1 Answer: {}", day11_result.0);
-    println!("Day 11, Part 2 Answer: {}", day11_result.1);
-    println!("---");
-
-    let day12_result = day12::solution();
-    println!("Day 12, Part 1 Answer: {}", day12_result.0);
-    println!("Day 12, Part 2 Answer: {}", day12_result.1);
-    println!("---");
-
-    let day13_result = day13::solution();
-    println!("Day 13, Part 1 Answer: {}", day13_result.0);
-    println!("Day 13, Part 2 Answer: {}", day13_result.1);
-    println!("---");
+    let failed_days = match answers {
+        Some(answers) => {
+            let (report, failed_days) = answers::report(&answers, &reports);
+            println!("{}", report);
+            failed_days
+        }
+        None => {
+            match args.format {
+                Format::Text => println!("{}", runner::summary(&reports)),
+                Format::Json => println!("{}", output::to_json(&output::records(&reports, &parts))),
+                Format::Csv => println!("{}", output::to_csv(&output::records(&reports, &parts))),
+            }
+            reports.iter().filter(|r| r.outcome.is_err()).count()
+        }
+    };
+    // the exit code is the number of failed days
+    if failed_days > 0 {
+        process::exit(cli::exit_code(failed_days));
+    }
 }
@@ -0,0 +1,215 @@
+use crate::runner::{stats, DayReport};
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("invalid format `{s}`, expected json, csv or text")),
+        }
+    }
+}
+
+/// One answer with the median time it took to compute it,
+/// or the error of the day it belongs to
+#[derive(Debug, PartialEq)]
+pub struct Record<'a> {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Result<&'a str, &'a str>,
+    pub duration: Duration,
+}
+
+/// Records of the `parts` of every day
+pub fn records<'a>(reports: &'a [DayReport], parts: &[u8]) -> Vec<Record<'a>> {
+    let mut records = Vec::new();
+    for report in reports {
+        let day_runs = match &report.outcome {
+            Ok(day_runs) => day_runs,
+            Err(e) => {
+                for &part in parts {
+                    let answer = Err(e.as_str());
+                    records.push(Record { day: report.day, part, answer, duration: Duration::ZERO });
+                }
+                continue;
+            }
+        };
+        for part in &day_runs[0].parts {
+            let samples: Vec<Duration> = day_runs
+                .iter()
+                .flat_map(|r| r.parts.iter().filter(|p| p.part == part.part))
+                .map(|p| p.duration)
+                .collect();
+            records.push(Record {
+                day: report.day,
+                part: part.part,
+                answer: Ok(&part.answer),
+                duration: stats(&samples).1,
+            });
+        }
+    }
+    records
+}
+
+/// Multiline answers (like the day 10 CRT screen) start on their own line
+pub fn text_answer(day: u8, part: u8, answer: &str) -> String {
+    if answer.contains('\n') {
+        format!("Day {day}, Part {part} Answer:\n{answer}")
+    } else {
+        format!("Day {day}, Part {part} Answer: {answer}")
+    }
+}
+
+pub(crate) fn json_string(s: &str) -> String {
+    let mut escaped = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+pub fn to_json(records: &[Record]) -> String {
+    let objects: Vec<String> = records
+        .iter()
+        .map(|r| {
+            let (answer, duration, error) = match r.answer {
+                Ok(answer) => (json_string(answer), r.duration.as_nanos().to_string(), "null".to_owned()),
+                Err(e) => ("null".to_owned(), "null".to_owned(), json_string(e)),
+            };
+            format!(
+                "  {{\"day\": {}, \"part\": {}, \"answer\": {}, \"duration_ns\": {}, \"error\": {}}}",
+                r.day, r.part, answer, duration, error
+            )
+        })
+        .collect();
+    if objects.is_empty() {
+        return "[]".to_owned();
+    }
+    format!("[\n{}\n]", objects.join(",\n"))
+}
+
+pub fn to_csv(records: &[Record]) -> String {
+    let mut lines = vec!["day,part,answer,duration_ns,error".to_owned()];
+    for r in records {
+        let (answer, duration, error) = match r.answer {
+            Ok(answer) => (csv_field(answer), r.duration.as_nanos().to_string(), String::new()),
+            Err(e) => (String::new(), String::new(), csv_field(e)),
+        };
+        lines.push(format!("{},{},{},{},{}", r.day, r.part, answer, duration, error));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::output::{self, Format, Record};
+    use crate::runner::{DayReport, DayRun, PartRun};
+    use std::time::Duration;
+
+    const SCREEN: &str = "##..\n#\"#,";
+
+    fn records() -> Vec<Record<'static>> {
+        vec![
+            Record { day: 1, part: 1, answer: Ok("68923"), duration: Duration::from_micros(14) },
+            Record { day: 10, part: 2, answer: Ok(SCREEN), duration: Duration::from_nanos(1500) },
+            Record { day: 13, part: 1, answer: Err("error: \"x\""), duration: Duration::ZERO },
+        ]
+    }
+
+    #[test]
+    fn format() {
+        assert_eq!("json".parse::<Format>(), Ok(Format::Json));
+        assert_eq!("csv".parse::<Format>(), Ok(Format::Csv));
+        assert_eq!("text".parse::<Format>(), Ok(Format::Text));
+        assert!("xml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn records_use_median_duration() {
+        let run = |micros: u64| DayRun {
+            day: 3,
+            read: Duration::ZERO,
+            parse: Duration::ZERO,
+            parts: vec![PartRun {
+                part: 2,
+                answer: "2644".to_owned(),
+                duration: Duration::from_micros(micros),
+            }],
+            warnings: Vec::new(),
+        };
+        let reports = vec![
+            DayReport { day: 3, outcome: Ok(vec![run(9), run(1), run(4)]) },
+            DayReport { day: 4, outcome: Err("oops".to_owned()) },
+        ];
+        assert_eq!(
+            output::records(&reports, &[2]),
+            vec![
+                Record { day: 3, part: 2, answer: Ok("2644"), duration: Duration::from_micros(4) },
+                Record { day: 4, part: 2, answer: Err("oops"), duration: Duration::ZERO },
+            ]
+        );
+    }
+
+    #[test]
+    fn text_answer() {
+        assert_eq!(output::text_answer(1, 1, "68923"), "Day 1, Part 1 Answer: 68923");
+        assert_eq!(output::text_answer(10, 2, "##\n.."), "Day 10, Part 2 Answer:\n##\n..");
+    }
+
+    #[test]
+    fn to_json() {
+        let json = output::to_json(&records());
+        println!("{}", json);
+        assert_eq!(
+            json,
+            "[
+  {\"day\": 1, \"part\": 1, \"answer\": \"68923\", \"duration_ns\": 14000, \"error\": null},
+  {\"day\": 10, \"part\": 2, \"answer\": \"##..\\n#\\\"#,\", \"duration_ns\": 1500, \"error\": null},
+  {\"day\": 13, \"part\": 1, \"answer\": null, \"duration_ns\": null, \"error\": \"error: \\\"x\\\"\"}
+]"
+        );
+        assert_eq!(output::to_json(&[]), "[]");
+    }
+
+    #[test]
+    fn to_csv() {
+        let csv = output::to_csv(&records());
+        println!("{}", csv);
+        assert_eq!(
+            csv,
+            "day,part,answer,duration_ns,error
+1,1,68923,14000,
+10,2,\"##..\n#\"\"#,\",1500,
+13,1,,,\"error: \"\"x\"\"\""
+        );
+    }
+}
@@ -0,0 +1,19 @@
+use crate::solution::Puzzle;
+use crate::{day1, day10, day11, day12, day13, day2, day3, day4, day5, day6, day7, day8, day9};
+
+/// Every solved day, in calendar order. A new day only needs its module and a line here.
+pub const DAYS: &[(u8, &dyn Puzzle)] = &[
+    (1, &day1::Day1),
+    (2, &day2::Day2),
+    (3, &day3::Day3),
+    (4, &day4::Day4),
+    (5, &day5::Day5),
+    (6, &day6::Day6),
+    (7, &day7::Day7),
+    (8, &day8::Day8),
+    (9, &day9::Day9),
+    (10, &day10::Day10),
+    (11, &day11::Day11),
+    (12, &day12::Day12),
+    (13, &day13::Day13),
+];
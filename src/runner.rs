@@ -0,0 +1,363 @@
+use crate::helpers::read_input;
+use crate::solution::Puzzle;
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone)]
+pub struct PartRun {
+    pub part: u8,
+    pub answer: String,
+    pub duration: Duration,
+}
+
+/// Answers and per phase timings of a single run of a day
+#[derive(Debug, Clone)]
+pub struct DayRun {
+    pub day: u8,
+    pub read: Duration,
+    pub parse: Duration,
+    pub parts: Vec<PartRun>,
+    /// Diagnostics of the problems skipped while parsing
+    pub warnings: Vec<String>,
+}
+
+/// Every run of a day, or the error that made it fail
+#[derive(Debug)]
+pub struct DayReport {
+    pub day: u8,
+    pub outcome: Result<Vec<DayRun>, String>,
+}
+
+impl DayReport {
+    /// First line of the error of a failed day
+    pub fn error_summary(&self) -> Option<&str> {
+        self.outcome.as_ref().err().map(|e| e.lines().next().unwrap_or(""))
+    }
+}
+
+impl DayRun {
+    pub fn total(&self) -> Duration {
+        self.read + self.parse + self.parts.iter().map(|p| p.duration).sum::<Duration>()
+    }
+
+    fn part_duration(&self, part: u8) -> Option<Duration> {
+        self.parts.iter().find(|p| p.part == part).map(|p| p.duration)
+    }
+}
+
+fn timed<T, F: FnOnce() -> T>(f: F) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+pub fn run_day(day: u8, puzzle: &dyn Puzzle, parts: &[u8], path: &Path) -> Result<DayRun, String> {
+    let (contents, read) = timed(|| read_input(path));
+    let contents = contents.map_err(|e| format!("error: {e}"))?;
+    let (input, parse) = timed(|| puzzle.parse(&contents));
+    let source = path.display().to_string();
+    let input = input.map_err(|e| e.diagnostic(&source, &contents))?;
+    let warnings = puzzle
+        .warnings(input.as_ref())
+        .iter()
+        .map(|w| w.warning(&source, &contents))
+        .collect();
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let (answer, duration) = timed(|| match part {
+                1 => puzzle.part1(input.as_ref()),
+                _ => puzzle.part2(input.as_ref()),
+            });
+            PartRun { part, answer, duration }
+        })
+        .collect();
+
+    Ok(DayRun { day, read, parse, parts, warnings })
+}
+
+thread_local! {
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+    /// Whether `run_isolated` is running on this thread
+    static ISOLATED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Inside `run_isolated`, replaces the default panic message on stderr: the message and
+/// location of a panic are kept so that the failed day can be reported with them instead.
+/// Panics anywhere else still go to the previous hook.
+pub fn install_panic_hook() {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if ISOLATED.with(Cell::get) {
+            LAST_PANIC.with(|last| *last.borrow_mut() = Some(info.to_string()));
+        } else {
+            previous(info);
+        }
+    }));
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = LAST_PANIC.with(|last| last.borrow_mut().take()) {
+        return message.replace('\n', " ");
+    }
+    match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+        (Some(message), _) => format!("panicked: {message}"),
+        (_, Some(message)) => format!("panicked: {message}"),
+        _ => "panicked".to_owned(),
+    }
+}
+
+/// Runs a day `repeat` times. Errors and panics are caught, so that one broken day
+/// can't keep the others from running.
+pub fn run_isolated(day: u8, puzzle: &dyn Puzzle, parts: &[u8], path: &Path, repeat: usize) -> DayReport {
+    let isolated = ISOLATED.with(|isolated| isolated.replace(true));
+    let runs = panic::catch_unwind(AssertUnwindSafe(|| {
+        (0..repeat)
+            .map(|_| run_day(day, puzzle, parts, path))
+            .collect::<Result<Vec<DayRun>, String>>()
+    }));
+    ISOLATED.with(|flag| flag.set(isolated));
+    let outcome = match runs {
+        Ok(outcome) => outcome,
+        Err(payload) => Err(panic_message(payload)),
+    };
+    DayReport { day, outcome }
+}
+
+/// (min, median, max) of the samples
+pub fn stats(samples: &[Duration]) -> (Duration, Duration, Duration) {
+    let mut sorted = samples.to_vec();
+    sorted.sort();
+    let mid = sorted.len() / 2;
+    let median = if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2
+    } else {
+        sorted[mid]
+    };
+    (sorted[0], median, sorted[sorted.len() - 1])
+}
+
+fn format_duration(d: Duration) -> String {
+    format!("{:.2?}", d)
+}
+
+fn format_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let widths: Vec<usize> = headers
+        .iter()
+        .enumerate()
+        .map(|(i, h)| rows.iter().map(|r| r[i].len()).max().unwrap_or(0).max(h.len()))
+        .collect();
+    let line = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(c, w)| format!("{c:>w$}"))
+            .collect::<Vec<String>>()
+            .join(" | ")
+    };
+
+    let mut table = vec![line(headers.to_vec())];
+    table.push(widths.iter().map(|w| "-".repeat(*w)).collect::<Vec<String>>().join("-+-"));
+    for row in rows {
+        table.push(line(row.iter().map(|c| c.as_str()).collect()));
+    }
+    table.join("\n")
+}
+
+/// Table with one row per day and one column per phase.
+/// With more than one run per day, the table has one row per day and phase
+/// with min, median and max instead.
+pub fn summary(reports: &[DayReport]) -> String {
+    let phase_samples = |day_runs: &[DayRun]| -> Vec<(&str, Vec<Duration>)> {
+        let mut phases = vec![
+            ("Read", day_runs.iter().map(|r| r.read).collect()),
+            ("Parse", day_runs.iter().map(|r| r.parse).collect()),
+        ];
+        for (part, name) in [(1, "Part 1"), (2, "Part 2")] {
+            let samples: Vec<Duration> = day_runs.iter().filter_map(|r| r.part_duration(part)).collect();
+            if !samples.is_empty() {
+                phases.push((name, samples));
+            }
+        }
+        phases.push(("Total", day_runs.iter().map(|r| r.total()).collect()));
+        phases
+    };
+
+    let failed_row = |day: u8, columns: usize| {
+        let mut row = vec![day.to_string(), "FAILED".to_owned()];
+        row.resize(columns, "-".to_owned());
+        row
+    };
+
+    let repeated = reports.iter().any(|r| matches!(&r.outcome, Ok(runs) if runs.len() > 1));
+    if !repeated {
+        let rows: Vec<Vec<String>> = reports
+            .iter()
+            .map(|report| {
+                let run = match &report.outcome {
+                    Ok(day_runs) => &day_runs[0],
+                    Err(_) => return failed_row(report.day, 6),
+                };
+                let cell = |d: Option<Duration>| d.map(format_duration).unwrap_or("-".to_owned());
+                vec![
+                    run.day.to_string(),
+                    format_duration(run.read),
+                    format_duration(run.parse),
+                    cell(run.part_duration(1)),
+                    cell(run.part_duration(2)),
+                    format_duration(run.total()),
+                ]
+            })
+            .collect();
+        return format_table(&["Day", "Read", "Parse", "Part 1", "Part 2", "Total"], &rows);
+    }
+
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    for report in reports {
+        let day_runs = match &report.outcome {
+            Ok(day_runs) => day_runs,
+            Err(_) => {
+                rows.push(failed_row(report.day, 5));
+                continue;
+            }
+        };
+        for (phase, samples) in phase_samples(day_runs) {
+            let (min, median, max) = stats(&samples);
+            rows.push(vec![
+                day_runs[0].day.to_string(),
+                phase.to_owned(),
+                format_duration(min),
+                format_duration(median),
+                format_duration(max),
+            ]);
+        }
+    }
+    format_table(&["Day", "Phase", "Min", "Median", "Max"], &rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day7::Day7;
+    use crate::runner;
+    use crate::solution::Solution;
+    use crate::error::ParseError;
+    use std::path::Path;
+    use std::time::Duration;
+
+    fn run(day: u8, millis: [u64; 3]) -> runner::DayRun {
+        runner::DayRun {
+            day,
+            read: Duration::from_millis(millis[0]),
+            parse: Duration::from_millis(millis[1]),
+            parts: vec![runner::PartRun {
+                part: 1,
+                answer: "42".to_owned(),
+                duration: Duration::from_millis(millis[2]),
+            }],
+            warnings: Vec::new(),
+        }
+    }
+
+    fn report(day: u8, outcome: Result<Vec<runner::DayRun>, String>) -> runner::DayReport {
+        runner::DayReport { day, outcome }
+    }
+
+    struct Panicking;
+
+    impl Solution for Panicking {
+        type Input = ();
+
+        fn parse(_input: &str) -> Result<Self::Input, ParseError> {
+            Ok(())
+        }
+
+        fn part1(_input: &Self::Input) -> String {
+            "1".to_owned()
+        }
+
+        fn part2(_input: &Self::Input) -> String {
+            panic!("part 2 is not solved")
+        }
+    }
+
+    #[test]
+    fn run_isolated() {
+        let path = Path::new("inputs/day10_test.txt");
+        let report = runner::run_isolated(10, &Panicking, &[1], path, 2);
+        assert_eq!(report.outcome.unwrap().len(), 2);
+
+        let report = runner::run_isolated(10, &Panicking, &[1, 2], path, 1);
+        let error = report.error_summary().unwrap();
+        assert!(error.contains("part 2 is not solved"), "{error}");
+
+        let report = runner::run_isolated(99, &Panicking, &[1, 2], Path::new("inputs/day99.txt"), 1);
+        assert!(report.error_summary().unwrap().starts_with("error: cannot read"));
+    }
+
+    #[test]
+    fn panic_hook() {
+        runner::install_panic_hook();
+        let report = runner::run_isolated(10, &Panicking, &[2], Path::new("inputs/day10_test.txt"), 1);
+        assert!(report.error_summary().unwrap().contains("src/runner.rs"));
+
+        // outside of `run_isolated` the panic goes to the previous hook and is not kept
+        let outside = std::panic::catch_unwind(|| panic!("outside of a day"));
+        assert!(outside.is_err());
+        assert!(runner::LAST_PANIC.with(|last| last.borrow().is_none()));
+        assert!(!runner::ISOLATED.with(|isolated| isolated.get()));
+    }
+
+    #[test]
+    fn warnings() {
+        let path = std::env::temp_dir().join(format!("aoc22-runner-warnings-{}.txt", std::process::id()));
+        std::fs::write(&path, "$ cd /\n$ ls\n10 a\n$ rm -rf a\n").unwrap();
+        let run = runner::run_day(7, &Day7, &[1], &path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(run.warnings.len(), 1);
+        let warning = &run.warnings[0];
+        assert!(warning.starts_with("warning: day 7, line 4, column 3: expected `cd` or `ls`, found `rm`"), "{warning}");
+        assert!(warning.ends_with("4 | $ rm -rf a\n  |   ^"), "{warning}");
+
+        let report = runner::run_isolated(10, &Panicking, &[1], Path::new("inputs/day10_test.txt"), 1);
+        assert!(report.outcome.unwrap()[0].warnings.is_empty());
+    }
+
+    #[test]
+    fn stats() {
+        let ms = Duration::from_millis;
+        assert_eq!(runner::stats(&[ms(3), ms(1), ms(2)]), (ms(1), ms(2), ms(3)));
+        assert_eq!(runner::stats(&[ms(4), ms(1), ms(2), ms(9)]), (ms(1), ms(3), ms(9)));
+        assert_eq!(runner::stats(&[ms(5)]), (ms(5), ms(5), ms(5)));
+    }
+
+    #[test]
+    fn summary() {
+        let table = runner::summary(&[report(1, Ok(vec![run(1, [1, 2, 3])]))]);
+        let lines: Vec<&str> = table.lines().collect();
+        println!("{}", table);
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("Day |"));
+        assert_eq!(
+            lines[2].split('|').map(|c| c.trim()).collect::<Vec<&str>>(),
+            vec!["1", "1.00ms", "2.00ms", "3.00ms", "-", "6.00ms"]
+        );
+    }
+
+    #[test]
+    fn summary_repeated() {
+        let runs = vec![run(7, [1, 1, 1]), run(7, [3, 3, 3]), run(7, [2, 2, 2])];
+        let table = runner::summary(&[report(7, Ok(runs)), report(8, Err("oops".to_owned()))]);
+        println!("{}", table);
+        // header, separator, Read, Parse, Part 1, Total, day 8
+        assert_eq!(table.lines().count(), 7);
+        assert!(table.lines().nth(2).unwrap().contains("1.00ms | 2.00ms | 3.00ms"));
+        assert!(table.lines().nth(5).unwrap().contains("3.00ms | 6.00ms | 9.00ms"));
+        assert_eq!(
+            table.lines().last().unwrap().split('|').map(|c| c.trim()).collect::<Vec<&str>>(),
+            vec!["8", "FAILED", "-", "-", "-"]
+        );
+    }
+}
@@ -0,0 +1,43 @@
+use crate::error::ParseError;
+use std::any::Any;
+
+/// One day of the calendar: the input is parsed once and both parts are solved from it.
+pub trait Solution {
+    type Input: 'static;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> String;
+    fn part2(input: &Self::Input) -> String;
+
+    /// Problems `parse` skipped over instead of failing, none by default
+    fn warnings(_input: &Self::Input) -> Vec<ParseError> {
+        Vec::new()
+    }
+}
+
+/// Object safe version of `Solution`, so days with different `Input` types fit in one registry.
+/// Implemented for every `Solution`.
+pub trait Puzzle {
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError>;
+    fn part1(&self, input: &dyn Any) -> String;
+    fn part2(&self, input: &dyn Any) -> String;
+    fn warnings(&self, input: &dyn Any) -> Vec<ParseError>;
+}
+
+impl<S: Solution> Puzzle for S {
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError> {
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn part1(&self, input: &dyn Any) -> String {
+        S::part1(input.downcast_ref().expect("input parsed by another day"))
+    }
+
+    fn part2(&self, input: &dyn Any) -> String {
+        S::part2(input.downcast_ref().expect("input parsed by another day"))
+    }
+
+    fn warnings(&self, input: &dyn Any) -> Vec<ParseError> {
+        S::warnings(input.downcast_ref().expect("input parsed by another day"))
+    }
+}